@@ -1,30 +1,54 @@
 #![feature(generic_const_exprs)]
 #![allow(incomplete_features)]
 
+use std::io::Write;
 use std::str::FromStr;
 
-use sigmars_lib::{Board, solve_dfs};
+use sigmars_lib::{Board, ProgressEvent, RenderStyle, Solution, SolveOutcome, Solver};
 
 fn main() {
-    let filename = std::env::args().nth(1);
-    if let Some(filename) = filename {
+    let (flags, args): (Vec<String>, Vec<String>) = std::env::args()
+        .skip(1)
+        .partition(|arg| arg.starts_with("--"));
+    let style = if flags.iter().any(|flag| flag == "--unicode") {
+        RenderStyle::Unicode
+    } else {
+        RenderStyle::Ascii
+    };
+    if let Some(filename) = args.first() {
         let filedata = std::fs::read_to_string(filename).expect("Failed to read file");
         let board = Board::<6>::from_str(&filedata).expect("Failed to parse board");
-        match solve_dfs(&board) {
-            Some(solution) => {
+        print!("{}", board.render(style, None));
+        println!();
+        let outcome = Solver::default().solve_with_progress(&board, render_progress);
+        // Move past the progress line before printing results.
+        eprintln!();
+        match outcome {
+            SolveOutcome::Solved(steps) => {
+                let solution = Solution::from_steps(&board, steps);
                 println!("Solution found with {} moves:", solution.len());
-                for match_set in solution {
-                    let msg = match_set
-                        .iter()
-                        .map(|c| format!("{:?}@({},{})", board.get_tile(c), c.row, c.col))
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    println!("{}", msg);
-                }
+                print!("{}", solution.to_clock_notation());
+            }
+            SolveOutcome::Unsolvable {
+                states_explored, ..
+            } => eprintln!("No solution exists ({} states explored)", states_explored),
+            SolveOutcome::TimedOut | SolveOutcome::Cancelled => {
+                eprintln!("Search stopped before finding a solution")
             }
-            None => eprintln!("No solution found"),
         }
     } else {
-        eprintln!("Usage: sigmars_cli <board_file>");
+        eprintln!("Usage: sigmars_cli [--unicode] <board_file>");
     }
 }
+
+fn render_progress(event: ProgressEvent) {
+    eprint!(
+        "\rExplored {} states ({:.0}/s), depth {} (max {}), {:.1}s elapsed",
+        event.states_explored,
+        event.states_per_sec,
+        event.depth,
+        event.max_depth,
+        event.elapsed.as_secs_f64()
+    );
+    let _ = std::io::stderr().flush();
+}
@@ -0,0 +1,110 @@
+use crate::board::Board;
+use crate::coord::BoardCoord;
+use crate::errors::BoardParseError;
+use crate::math::{board_area, row_count, row_length};
+use crate::tile::Tile;
+
+/// The characters used for tiles in the board text format.
+///
+/// Every tile uses its [`Tile::to_char`] character unless given another with
+/// [`TileAlphabet::with`], so other tools' letters can be read and written directly.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TileAlphabet {
+    overrides: Vec<(Tile, char)>,
+}
+impl TileAlphabet {
+    /// Use `c` for `tile`, replacing any earlier choice for either.
+    pub fn with(mut self, tile: Tile, c: char) -> Self {
+        self.overrides.retain(|(t, oc)| *t != tile && *oc != c);
+        self.overrides.push((tile, c));
+        self
+    }
+
+    pub fn char_for(&self, tile: Tile) -> char {
+        self.overrides
+            .iter()
+            .find(|(t, _)| *t == tile)
+            .map_or_else(|| tile.to_char(), |(_, c)| *c)
+    }
+    /// The tile written as `c`. A tile's usual character no longer reads as it once it is given
+    /// another, and chosen characters take precedence over usual ones.
+    pub fn tile_for(&self, c: char) -> Result<Tile, BoardParseError> {
+        if let Some((tile, _)) = self.overrides.iter().find(|(_, oc)| *oc == c) {
+            return Ok(*tile);
+        }
+        let tile = Tile::try_from(c)?;
+        if self.overrides.iter().any(|(t, _)| *t == tile) {
+            return Err(BoardParseError::UnexpectedTileCharacter(c));
+        }
+        Ok(tile)
+    }
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Parse the board text format, reading tiles with `alphabet`.
+    pub fn from_str_with(s: &str, alphabet: &TileAlphabet) -> Result<Self, BoardParseError> {
+        let mut tiles = [Tile::Empty; board_area::<S>()];
+        let line_count = s.matches("\n").count() + 1;
+        if line_count != row_count::<S>() {
+            return Err(BoardParseError::InvalidRowCount(
+                row_count::<S>(),
+                line_count,
+            ));
+        }
+
+        for (row_idx, line) in s.lines().enumerate() {
+            let line_len = line.chars().count();
+            if row_length::<S>(row_idx) != line_len {
+                return Err(BoardParseError::InvalidRowLength(
+                    row_length::<S>(row_idx),
+                    line_len,
+                ));
+            }
+
+            for (col_idx, c) in line.chars().enumerate() {
+                tiles[BoardCoord::new(row_idx, col_idx).as_index::<S>()] = alphabet.tile_for(c)?;
+            }
+        }
+
+        Ok(Self::from_tiles(tiles))
+    }
+
+    /// Write the board in the text format, one row per line, with tiles written by `alphabet`.
+    pub fn to_string_with(&self, alphabet: &TileAlphabet) -> String {
+        self.rows()
+            .map(|row| row.iter().map(|tile| alphabet.char_for(*tile)).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::BinaryTile;
+
+    #[test]
+    fn test_custom_alphabet_round_trip() {
+        let alphabet = TileAlphabet::default()
+            .with(Tile::Theta, 'S')
+            .with(Tile::Binary(BinaryTile::Life), 'V')
+            .with(Tile::Binary(BinaryTile::Death), 'M')
+            .with(Tile::Gold, 'G');
+        let text = "SV\nMGF\n__";
+        let board = Board::<2>::from_str_with(text, &alphabet).unwrap();
+        assert_eq!(board, "TL\nD5F\n__".parse().unwrap());
+        assert_eq!(board.to_string_with(&alphabet), text);
+        assert_eq!(
+            board.to_string_with(&TileAlphabet::default()),
+            "TL\nD5F\n__"
+        );
+
+        assert_eq!(
+            Board::<2>::from_str_with("T_\n___\n__", &alphabet),
+            Err(BoardParseError::UnexpectedTileCharacter('T'))
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::board::Board;
+use crate::coord::{BoardCoord, MatchSet};
+use crate::math::board_area;
+use crate::search::{SearchProgress, SearchState};
+use crate::solve::Solvable;
+use crate::solver::SolverOptions;
+use crate::tile::Tile;
+
+/// How many states a hint may explore looking for a full solution before falling back to lookahead.
+const HINT_STATE_BUDGET: usize = 100_000;
+/// Number of rollouts played per move by [`Board::evaluate_moves`].
+const DEFAULT_ROLLOUTS: usize = 32;
+/// Seed used by [`Board::evaluate_moves`], so repeated evaluations of a board agree.
+const DEFAULT_ROLLOUT_SEED: u64 = 0x0053_4947_4d41_5253;
+
+/// A legal move, scored by how likely it is to keep the board solvable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveEvaluation {
+    pub match_set: MatchSet,
+    /// Fraction of random rollouts after this move which cleared the board, from 0 to 1.
+    pub survivability: f64,
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Recommend a single next move that keeps the board solvable.
+    ///
+    /// This first tries a bounded full search and suggests the first move of its solution.
+    /// If that doesn't finish, it falls back to the highest-priority move which doesn't
+    /// leave the board provably unsolvable. Returns `None` if there are no moves left.
+    pub fn hint(&self) -> Option<MatchSet> {
+        let mut search = SearchState::new(self, &SolverOptions::default());
+        if let SearchProgress::Solved(solution) = search.advance(HINT_STATE_BUDGET)
+            && let Some(step) = solution.into_iter().next()
+        {
+            return Some(step);
+        }
+
+        let steps = self.next_steps();
+        steps
+            .iter()
+            .find(|step| !self.apply_step(step).is_dead_end())
+            .or(steps.first())
+            .cloned()
+    }
+
+    /// Score every legal move by playing random rollouts after it, most survivable first.
+    pub fn evaluate_moves(&self) -> Vec<MoveEvaluation> {
+        self.evaluate_moves_with(
+            DEFAULT_ROLLOUTS,
+            &mut StdRng::seed_from_u64(DEFAULT_ROLLOUT_SEED),
+        )
+    }
+
+    /// Score every legal move by playing `rollouts` random rollouts after it using `rng`,
+    /// most survivable first. Moves with equal scores keep their priority order.
+    pub fn evaluate_moves_with(&self, rollouts: usize, rng: &mut impl Rng) -> Vec<MoveEvaluation> {
+        let mut evaluations: Vec<MoveEvaluation> = self
+            .next_steps()
+            .into_iter()
+            .map(|match_set| {
+                let after = self.apply_step(&match_set);
+                let survived = (0..rollouts).filter(|_| after.rollout(rng)).count();
+                MoveEvaluation {
+                    match_set,
+                    survivability: survived as f64 / rollouts.max(1) as f64,
+                }
+            })
+            .collect();
+        evaluations.sort_by(|a, b| b.survivability.total_cmp(&a.survivability));
+        evaluations
+    }
+
+    /// List the legal moves which are always safe to play immediately.
+    ///
+    /// A move is forced if it clears a gold, or if one of its tiles has no other possible partner
+    /// left on the board, so every solution must make it eventually. Since clearing tiles never
+    /// makes another tile unselectable, making it now can't lose a solvable board.
+    ///
+    /// The first move of the [`metal_chain`](Board::metal_chain) is also forced when there is one.
+    pub fn forced_moves(&self) -> Vec<MatchSet> {
+        let mut forced: Vec<MatchSet> = self
+            .next_steps()
+            .into_iter()
+            .filter(|match_set| {
+                match_set.len() == 1 || match_set.iter().any(|c| self.partner_count(c) == 1)
+            })
+            .collect();
+        if let Some(first) = self
+            .metal_chain()
+            .and_then(|chain| chain.into_iter().next())
+            && !forced.contains(&first)
+        {
+            forced.push(first);
+        }
+        forced
+    }
+
+    /// Plan the rest of the quicksilver/metal chain as a single sequence of moves, from lead up.
+    ///
+    /// This only succeeds once every remaining metal and quicksilver is already selectable, since
+    /// then any quicksilver can take any metal and clearing them can't block anything else.
+    pub fn metal_chain(&self) -> Option<Vec<MatchSet>> {
+        let mut metals = Vec::new();
+        let mut quicksilver = Vec::new();
+        for (coord, tile) in self.nonempty_tiles() {
+            match tile {
+                Tile::Metal(metal) => metals.push((*metal, coord)),
+                Tile::Quicksilver => quicksilver.push(coord),
+                _ => {}
+            }
+        }
+        if metals.is_empty()
+            || metals.len() != quicksilver.len()
+            || !metals
+                .iter()
+                .map(|(_, coord)| coord)
+                .chain(&quicksilver)
+                .all(|coord| self.is_selectable(coord))
+        {
+            return None;
+        }
+
+        metals.sort();
+        Some(
+            metals
+                .into_iter()
+                .zip(quicksilver)
+                .map(|((_, metal), quicksilver)| MatchSet::from([metal, quicksilver]))
+                .collect(),
+        )
+    }
+
+    /// Count the tiles anywhere on the board which the tile at `coord` could ever be matched with.
+    fn partner_count(&self, coord: &BoardCoord) -> usize {
+        let tile = *self.get_tile(coord);
+        self.nonempty_tiles()
+            .filter(|(c, other)| c != coord && can_ever_match(tile, **other))
+            .count()
+    }
+
+    /// List the tiles which can never be cleared, in board order.
+    ///
+    /// A tile is dead if no tile it could be matched with is left alive, or if it is hemmed in by
+    /// dead tiles so that it can never become selectable. Any dead tile means the board is lost.
+    pub fn dead_tiles(&self) -> Vec<BoardCoord> {
+        let tiles = self.tiles().as_slice();
+        let mut dead = [false; board_area::<S>()];
+        let is_alive = |dead: &[bool], idx: usize| tiles[idx].needs_clearing() && !dead[idx];
+
+        // Each newly dead tile can doom its partners and neighbors, so repeat until nothing changes.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for idx in 0..board_area::<S>() {
+                if !is_alive(&dead, idx) {
+                    continue;
+                }
+                let tile = tiles[idx];
+                let has_partner = tile == Tile::Gold
+                    || (0..board_area::<S>()).any(|other| {
+                        other != idx && is_alive(&dead, other) && can_ever_match(tile, tiles[other])
+                    });
+                // Every live neighbor might still be cleared, but dead and blocked ones never will.
+                let clearable = BoardCoord::from_index::<S>(idx)
+                    .neighbor_coords::<S>()
+                    .map(|c| {
+                        c.is_none_or(|c| {
+                            let idx = c.as_index::<S>();
+                            !dead[idx] && tiles[idx] != Tile::Blocked
+                        })
+                    });
+                let can_open = (0..6).any(|i| (0..3).all(|j| clearable[(i + j) % 6]));
+
+                if !has_partner || !can_open {
+                    dead[idx] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        (0..board_area::<S>())
+            .filter(|idx| dead[*idx])
+            .map(BoardCoord::from_index::<S>)
+            .collect()
+    }
+
+    /// Group the non-empty tiles into clusters of tiles connected through their neighbors.
+    ///
+    /// Each cluster is in board order, and clusters are ordered by their first tile.
+    pub fn components(&self) -> Vec<Vec<BoardCoord>> {
+        let mut visited = [false; board_area::<S>()];
+        let mut components = Vec::new();
+        for (start, _) in self.nonempty_tiles() {
+            if visited[start.as_index::<S>()] {
+                continue;
+            }
+            visited[start.as_index::<S>()] = true;
+            let mut component = vec![start];
+            let mut frontier = vec![start];
+            while let Some(coord) = frontier.pop() {
+                for neighbor in coord.neighbor_coords::<S>().into_iter().flatten() {
+                    let idx = neighbor.as_index::<S>();
+                    if !visited[idx] && *self.get_tile(neighbor) != Tile::Empty {
+                        visited[idx] = true;
+                        component.push(neighbor);
+                        frontier.push(neighbor);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Play uniformly random legal moves until the board is cleared or stuck,
+    /// returning whether it was cleared.
+    fn rollout(&self, rng: &mut impl Rng) -> bool {
+        let mut board = self.clone();
+        loop {
+            if board.is_goal() {
+                return true;
+            }
+            if board.is_dead_end() {
+                return false;
+            }
+            match board.next_steps().choose(rng) {
+                Some(step) => board.remove_match_set(step),
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Whether tiles `a` and `b` could be cleared together in a pair at some point.
+fn can_ever_match(a: Tile, b: Tile) -> bool {
+    match (a, b) {
+        (Tile::Element(a), Tile::Element(b)) => a == b,
+        (Tile::Element(_) | Tile::Theta, Tile::Element(_) | Tile::Theta) => true,
+        (Tile::Binary(a), Tile::Binary(b)) => a != b,
+        (Tile::Metal(_), Tile::Quicksilver) | (Tile::Quicksilver, Tile::Metal(_)) => true,
+        #[cfg(feature = "quintessence")]
+        (Tile::Quintessence, Tile::Element(_)) | (Tile::Element(_), Tile::Quintessence) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::{BinaryTile, ElementTile, MetalTile};
+
+    #[test]
+    fn test_hint_on_empty_board() {
+        assert_eq!(Board::<3>::empty().hint(), None);
+    }
+
+    #[test]
+    fn test_hint_keeps_board_solvable() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 2), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(2, 0), Tile::Theta),
+            (BoardCoord::new(2, 4), Tile::Theta),
+            (BoardCoord::new(4, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(4, 2), Tile::Element(ElementTile::Air)),
+        ]);
+
+        let hint = board.hint().expect("Board has moves left");
+        assert!(board.find_match_sets().contains(&hint));
+        assert!(crate::solve::solve_dfs(&board.without_match_set(&hint)).is_solved());
+    }
+
+    #[test]
+    fn test_evaluate_moves_ranks_trap_last() {
+        // Pairing the two salts leaves fire and water with nothing to match.
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 2), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(4, 0), Tile::Theta),
+            (BoardCoord::new(4, 2), Tile::Theta),
+        ]);
+
+        let evaluations = board.evaluate_moves();
+        assert_eq!(evaluations.len(), 5);
+        let trap = evaluations.last().unwrap();
+        assert!(trap.match_set.contains(&BoardCoord::new(4, 0)));
+        assert!(trap.match_set.contains(&BoardCoord::new(4, 2)));
+        assert_eq!(trap.survivability, 0.0);
+        assert_eq!(evaluations[0].survivability, 1.0);
+    }
+
+    #[test]
+    fn test_forced_moves() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Binary(BinaryTile::Life)),
+            (BoardCoord::new(0, 2), Tile::Binary(BinaryTile::Death)),
+            (BoardCoord::new(2, 2), Tile::Gold),
+            (BoardCoord::new(4, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(4, 2), Tile::Theta),
+            (BoardCoord::new(2, 0), Tile::Theta),
+            (BoardCoord::new(2, 4), Tile::Element(ElementTile::Fire)),
+        ]);
+
+        let forced = board.forced_moves();
+        // The gold and the only vitae/mors pair, but none of the fire/salt pairings.
+        assert_eq!(forced.len(), 2);
+        assert!(forced.contains(&MatchSet::from([BoardCoord::new(2, 2)])));
+        assert!(forced.contains(&MatchSet::from([
+            BoardCoord::new(0, 0),
+            BoardCoord::new(0, 2)
+        ])));
+    }
+
+    #[test]
+    fn test_dead_tiles_spread() {
+        let board = Board::<3>::from_iter([
+            // Unmatched elements around the center, with no salt to pair them with.
+            (BoardCoord::new(1, 1), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 3), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(3, 1), Tile::Element(ElementTile::Air)),
+            // Hemmed in by the elements, so the vitae and its only partner are lost too.
+            (BoardCoord::new(2, 2), Tile::Binary(BinaryTile::Life)),
+            (BoardCoord::new(1, 2), Tile::Binary(BinaryTile::Death)),
+            (BoardCoord::new(3, 2), Tile::Gold),
+            (BoardCoord::new(2, 1), Tile::Gold),
+        ]);
+
+        assert_eq!(
+            board.dead_tiles(),
+            vec![
+                BoardCoord::new(1, 1),
+                BoardCoord::new(1, 2),
+                BoardCoord::new(2, 2),
+                BoardCoord::new(2, 3),
+                BoardCoord::new(3, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metal_chain() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Metal(MetalTile::Tin)),
+            (BoardCoord::new(0, 2), Tile::Metal(MetalTile::Lead)),
+            (BoardCoord::new(4, 0), Tile::Quicksilver),
+            (BoardCoord::new(4, 2), Tile::Quicksilver),
+        ]);
+
+        let chain = board.metal_chain().expect("Every metal is free");
+        assert_eq!(
+            chain,
+            vec![
+                MatchSet::from([BoardCoord::new(0, 2), BoardCoord::new(4, 0)]),
+                MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(4, 2)]),
+            ]
+        );
+        assert_eq!(board.forced_moves(), vec![chain[0].clone()]);
+
+        // Bury the tin in the middle of the board, so the chain can't be planned yet.
+        let mut board = board;
+        board.remove_tile(BoardCoord::new(0, 0));
+        board.set_tile(BoardCoord::new(2, 2), Tile::Metal(MetalTile::Tin));
+        for coord in BoardCoord::new(2, 2)
+            .neighbor_coords::<3>()
+            .into_iter()
+            .flatten()
+        {
+            board.set_tile(coord, Tile::Gold);
+        }
+        assert_eq!(board.metal_chain(), None);
+    }
+
+    #[test]
+    fn test_components() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(1, 1), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 1), Tile::Theta),
+            (BoardCoord::new(4, 2), Tile::Gold),
+        ]);
+        assert_eq!(
+            board.components(),
+            vec![
+                vec![
+                    BoardCoord::new(0, 0),
+                    BoardCoord::new(0, 1),
+                    BoardCoord::new(1, 1)
+                ],
+                vec![BoardCoord::new(4, 2)],
+            ]
+        );
+        assert!(Board::<3>::empty().components().is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::solve::Solvable;
+
+/// A sequence of steps to a goal state, found by [`astar`], [`weighted_astar`], or [`dijkstra`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostedSolution<G: Solvable> {
+    pub steps: Vec<G::Step>,
+    /// Sum of the `step_cost` of every step.
+    pub cost: u32,
+    /// The solution costs at most this many times as much as an optimal one,
+    /// provided the heuristic never overestimates.
+    pub suboptimality: f64,
+}
+
+/// The cost of taking a step from a state, for solving by a custom objective with [`solve_min_cost`].
+///
+/// Any `Fn(&G, &G::Step) -> u32` closure is a `MoveCost`.
+pub trait MoveCost<G: Solvable> {
+    fn cost(&self, state: &G, step: &G::Step) -> u32;
+}
+impl<G: Solvable, F: Fn(&G, &G::Step) -> u32> MoveCost<G> for F {
+    fn cost(&self, state: &G, step: &G::Step) -> u32 {
+        self(state, step)
+    }
+}
+
+/// Every step costs 1, so the cheapest solution is the shortest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FewestMoves;
+impl<G: Solvable> MoveCost<G> for FewestMoves {
+    fn cost(&self, _state: &G, _step: &G::Step) -> u32 {
+        1
+    }
+}
+
+struct Node<G: Solvable> {
+    state: G,
+    /// Index of the node this one was reached from, and the step taken to get here.
+    parent: Option<(usize, G::Step)>,
+}
+
+/// Find a lowest-cost solution with A* search.
+///
+/// `step_cost` gives the cost of applying a step to a state, and `heuristic` estimates the remaining
+/// cost from a state to a goal. The solution is only guaranteed to be optimal if `heuristic` never
+/// overestimates. States proven unsolvable by [`Solvable::is_dead_end`] are never expanded.
+pub fn astar<G: Solvable>(
+    start: &G,
+    step_cost: impl Fn(&G, &G::Step) -> u32,
+    heuristic: impl Fn(&G) -> u32,
+) -> Option<CostedSolution<G>> {
+    weighted_astar(start, step_cost, heuristic, 1.0)
+}
+
+/// Find a solution with weighted A* search, which expands states by `cost + weight * heuristic`.
+///
+/// A `weight` above 1 trusts the heuristic more, usually finding a solution much faster at the
+/// price of a solution up to `weight` times as costly as the optimal one. The bound is returned
+/// as [`CostedSolution::suboptimality`]. Weights below 1 are treated as 1.
+pub fn weighted_astar<G: Solvable>(
+    start: &G,
+    step_cost: impl Fn(&G, &G::Step) -> u32,
+    heuristic: impl Fn(&G) -> u32,
+    weight: f64,
+) -> Option<CostedSolution<G>> {
+    let weight = weight.max(1.0);
+    let estimate =
+        |cost: u32, state: &G| cost as u64 + (weight * heuristic(state) as f64).ceil() as u64;
+
+    let mut nodes = vec![Node {
+        state: start.clone(),
+        parent: None,
+    }];
+    let mut best_cost = HashMap::from([(start.clone(), 0)]);
+    // Expand the lowest estimated total first, preferring deeper nodes (higher cost so far) on ties.
+    let mut open = BinaryHeap::from([(Reverse(estimate(0, start)), 0, Reverse(0usize))]);
+
+    while let Some((_, cost, Reverse(idx))) = open.pop() {
+        let state = nodes[idx].state.clone();
+        if best_cost.get(&state).is_some_and(|&best| best < cost) {
+            // A cheaper path to this state was found after this entry was queued.
+            continue;
+        }
+        if state.is_goal() {
+            return Some(CostedSolution {
+                steps: path(&nodes, idx),
+                cost,
+                suboptimality: weight,
+            });
+        }
+        if state.is_dead_end() {
+            continue;
+        }
+
+        for step in state.next_steps() {
+            let next = state.apply_step(&step);
+            let next_cost = cost + step_cost(&state, &step);
+            match best_cost.entry(next.clone()) {
+                Entry::Occupied(entry) if *entry.get() <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert(next_cost);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                }
+            }
+            let estimate = estimate(next_cost, &next);
+            nodes.push(Node {
+                state: next,
+                parent: Some((idx, step)),
+            });
+            open.push((Reverse(estimate), next_cost, Reverse(nodes.len() - 1)));
+        }
+    }
+    None
+}
+
+/// Find a lowest-cost solution with Dijkstra's algorithm, i.e. [`astar`] without a heuristic.
+pub fn dijkstra<G: Solvable>(
+    start: &G,
+    step_cost: impl Fn(&G, &G::Step) -> u32,
+) -> Option<CostedSolution<G>> {
+    astar(start, step_cost, |_| 0)
+}
+
+/// Find a solution minimizing the total of `cost` over its steps.
+///
+/// This is an exhaustive [`dijkstra`] search, so it is only practical when `cost` leaves few
+/// states tied at the cheapest cost, or for small boards.
+pub fn solve_min_cost<G: Solvable>(
+    start: &G,
+    cost: &impl MoveCost<G>,
+) -> Option<CostedSolution<G>> {
+    dijkstra(start, |state, step| cost.cost(state, step))
+}
+
+/// Walk parent links back from `idx` to collect the steps taken from the start.
+fn path<G: Solvable>(nodes: &[Node<G>], mut idx: usize) -> Vec<G::Step> {
+    let mut steps = Vec::new();
+    while let Some((parent, step)) = &nodes[idx].parent {
+        steps.push(step.clone());
+        idx = *parent;
+    }
+    steps.reverse();
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walk from a number down to zero, either one or three at a time.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Countdown(u32);
+    impl Solvable for Countdown {
+        type Step = u32;
+
+        fn is_goal(&self) -> bool {
+            self.0 == 0
+        }
+        fn next_steps(&self) -> Vec<u32> {
+            [1, 3].into_iter().filter(|s| *s <= self.0).collect()
+        }
+        fn apply_step(&self, step: &u32) -> Self {
+            Countdown(self.0 - step)
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_finds_cheapest_path() {
+        // Steps of 3 cost more than three steps of 1.
+        let solution = dijkstra(&Countdown(6), |_, step| if *step == 3 { 4 } else { 1 }).unwrap();
+        assert_eq!(solution.steps, vec![1; 6]);
+        assert_eq!(solution.cost, 6);
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let cost = |_: &Countdown, _: &u32| 1;
+        let solution = astar(&Countdown(7), cost, |c| c.0.div_ceil(3)).unwrap();
+        assert_eq!(solution.cost, dijkstra(&Countdown(7), cost).unwrap().cost);
+        assert_eq!(solution.cost, 3);
+    }
+
+    #[test]
+    fn test_solve_min_cost() {
+        let solution = solve_min_cost(&Countdown(7), &FewestMoves).unwrap();
+        assert_eq!(solution.steps.len(), 3);
+        let solution = solve_min_cost(&Countdown(7), &|_: &Countdown, step: &u32| *step).unwrap();
+        assert_eq!(solution.cost, 7);
+    }
+
+    #[test]
+    fn test_weighted_astar_reports_bound() {
+        let cost = |_: &Countdown, step: &u32| if *step == 3 { 4 } else { 1 };
+        let solution = weighted_astar(&Countdown(9), cost, |c| c.0.div_ceil(3), 3.0).unwrap();
+        let optimal = dijkstra(&Countdown(9), cost).unwrap();
+        assert_eq!(solution.suboptimality, 3.0);
+        assert!(solution.cost as f64 <= optimal.cost as f64 * solution.suboptimality);
+    }
+}
@@ -1,17 +1,56 @@
 use std::collections::HashSet;
 use std::str::FromStr;
 
-use crate::coord::{BoardCoord, MatchSet, MatchSets};
-use crate::errors::BoardParseError;
-use crate::math::{board_area, row_count, row_length};
-use crate::tile::{Matchable, Tile};
+use rand::Rng;
+use rand::seq::SliceRandom;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+use crate::alphabet::TileAlphabet;
+use crate::coord::{BoardCoord, HexDirection, IntoBoardCoord, MatchSet, MatchSets};
+use crate::errors::{BoardParseError, CoordError, InvalidMoveError, OverlapError, TileCountError};
+use crate::math::{board_area, coords, row_count, row_length};
+use crate::ruleset::ClassicRules;
+use crate::solve::MoveType;
+use crate::tile::Tile;
+
+#[derive(Clone)]
 pub struct Board<const S: usize>
 where
     [(); board_area::<S>()]: Sized,
 {
     tiles: [Tile; board_area::<S>()],
+    /// Whether each tile is non-empty and selectable, kept up to date by [`Board::set_tile`].
+    selectable: [bool; board_area::<S>()],
+    /// Number of tiles which need clearing.
+    remaining: usize,
+    /// Number of `true` entries in `selectable`.
+    free: usize,
+}
+
+// The cached counts and selectability are determined by the tiles, so only the tiles are compared and hashed.
+impl<const S: usize> PartialEq for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.tiles == other.tiles
+    }
+}
+impl<const S: usize> Eq for Board<S> where [(); board_area::<S>()]: Sized {}
+impl<const S: usize> std::hash::Hash for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.tiles.hash(state);
+    }
+}
+impl<const S: usize> std::fmt::Debug for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Board").field("tiles", &self.tiles).finish()
+    }
 }
 
 impl<const S: usize> Board<S>
@@ -21,23 +60,100 @@ where
     pub const fn empty() -> Self {
         Self {
             tiles: [Tile::Empty; board_area::<S>()],
+            selectable: [false; board_area::<S>()],
+            remaining: 0,
+            free: 0,
         }
     }
     pub fn from_tiles(tiles: [Tile; board_area::<S>()]) -> Self {
-        Self { tiles }
+        let mut board = Self {
+            tiles,
+            selectable: [false; board_area::<S>()],
+            remaining: tiles.iter().filter(|tile| tile.needs_clearing()).count(),
+            free: 0,
+        };
+        for coord in coords::<S>() {
+            board.refresh_selectable(coord);
+        }
+        board
     }
 
+    /// Whether every tile has been cleared, ignoring [`Tile::Blocked`] cells.
     pub fn is_empty(&self) -> bool {
-        self.tiles.iter().all(|t| *t == Tile::Empty)
+        self.remaining == 0
+    }
+    /// Number of tiles left to clear, not counting [`Tile::Blocked`] cells.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+    /// Number of tiles which are currently selectable.
+    pub fn free_count(&self) -> usize {
+        self.free
     }
 
-    pub fn get_tile(&self, coord: &BoardCoord) -> &Tile {
-        &self.tiles[coord.as_index::<S>()]
+    /// The tile at `coord`, which may be a [`BoardCoord`], [`AxialCoord`](crate::AxialCoord),
+    /// or [`CubeCoord`](crate::CubeCoord).
+    pub fn get_tile(&self, coord: impl IntoBoardCoord) -> &Tile {
+        &self.tiles[coord.into_board_coord::<S>().as_index::<S>()]
     }
-    pub fn set_tile(&mut self, coord: &BoardCoord, tile: Tile) {
-        self.tiles[coord.as_index::<S>()] = tile;
+    /// Put `tile` at `coord`, which may be any coordinate type [`Board::get_tile`] accepts.
+    pub fn set_tile(&mut self, coord: impl IntoBoardCoord, tile: Tile) {
+        let coord = coord.into_board_coord::<S>();
+        let old = std::mem::replace(&mut self.tiles[coord.as_index::<S>()], tile);
+        self.remaining += tile.needs_clearing() as usize;
+        self.remaining -= old.needs_clearing() as usize;
+        // Only this tile and its neighbors can change selectability.
+        self.refresh_selectable(coord);
+        for neighbor in coord.neighbor_coords::<S>().into_iter().flatten() {
+            self.refresh_selectable(neighbor);
+        }
     }
-    pub fn remove_tile(&mut self, coord: &BoardCoord) {
+    /// Move the tile at each index `idx` to `to(idx)`, which must be a symmetry of the board.
+    /// Symmetries keep neighbors adjacent, so the selectability cache moves along with the tiles.
+    pub(crate) fn permuted(&self, to: impl Fn(usize) -> usize) -> Self {
+        let mut board = Self::empty();
+        for idx in 0..board_area::<S>() {
+            board.tiles[to(idx)] = self.tiles[idx];
+            board.selectable[to(idx)] = self.selectable[idx];
+        }
+        board.remaining = self.remaining;
+        board.free = self.free;
+        board
+    }
+    fn refresh_selectable(&mut self, coord: BoardCoord) {
+        let idx = coord.as_index::<S>();
+        let selectable = self.tiles[idx].needs_clearing() && self.is_selectable(&coord);
+        if selectable != self.selectable[idx] {
+            self.selectable[idx] = selectable;
+            if selectable {
+                self.free += 1;
+            } else {
+                self.free -= 1;
+            }
+        }
+    }
+    /// Whether `coord` is a position on this size of board.
+    pub fn contains(coord: &BoardCoord) -> bool {
+        coord.is_on_board::<S>()
+    }
+    /// The tile at `coord`, or an error if `coord` is off the board.
+    pub fn try_get_tile(&self, coord: &BoardCoord) -> Result<&Tile, CoordError> {
+        if coord.is_on_board::<S>() {
+            Ok(self.get_tile(coord))
+        } else {
+            Err(CoordError::OffBoard(*coord, S))
+        }
+    }
+    /// Set the tile at `coord`, or return an error without changing the board if `coord` is off it.
+    pub fn try_set_tile(&mut self, coord: &BoardCoord, tile: Tile) -> Result<(), CoordError> {
+        if coord.is_on_board::<S>() {
+            self.set_tile(coord, tile);
+            Ok(())
+        } else {
+            Err(CoordError::OffBoard(*coord, S))
+        }
+    }
+    pub fn remove_tile(&mut self, coord: impl IntoBoardCoord) {
         self.set_tile(coord, Tile::Empty);
     }
     pub fn remove_match_set(&mut self, match_set: &MatchSet) {
@@ -45,15 +161,91 @@ where
             self.remove_tile(coord);
         }
     }
+    /// Remove `match_set` from the board, if it is a legal move.
+    ///
+    /// Unlike [`Board::remove_match_set`], this checks that every tile is selectable and that the
+    /// tiles match each other, and leaves the board untouched if they don't.
+    pub fn apply_match(&mut self, match_set: &MatchSet) -> Result<(), InvalidMoveError> {
+        match_set.validate(self)?;
+        self.remove_match_set(match_set);
+        Ok(())
+    }
     pub fn without_match_set(&self, match_set: &MatchSet) -> Self {
         let mut new_board = self.clone();
         new_board.remove_match_set(match_set);
         new_board
     }
 
+    /// Redistribute the tiles on this board randomly over the cells they occupy, using `rng`.
+    /// Empty cells stay empty.
+    pub fn shuffled(&self, rng: &mut impl Rng) -> Self {
+        let (coords, mut tiles): (Vec<BoardCoord>, Vec<Tile>) = self
+            .nonempty_tiles()
+            .map(|(coord, tile)| (coord, *tile))
+            .unzip();
+        tiles.shuffle(rng);
+        coords.into_iter().zip(tiles).collect()
+    }
+
+    /// Combine the tiles of this board and `other`, failing if both have different tiles in one cell.
+    pub fn overlay(&self, other: &Self) -> Result<Self, OverlapError> {
+        let mut tiles = self.tiles;
+        for (idx, (ours, theirs)) in self.tiles.iter().zip(&other.tiles).enumerate() {
+            match (ours, theirs) {
+                (_, Tile::Empty) => {}
+                (Tile::Empty, _) => tiles[idx] = *theirs,
+                _ if ours == theirs => {}
+                _ => {
+                    return Err(OverlapError {
+                        coord: BoardCoord::from_index::<S>(idx),
+                        ours: *ours,
+                        theirs: *theirs,
+                    });
+                }
+            }
+        }
+        Ok(Self::from_tiles(tiles))
+    }
+
+    /// A 64-bit hash of this board which is stable across versions and platforms.
+    ///
+    /// This is the 64-bit FNV-1a hash of the board size `S` as one byte, followed by the text-format
+    /// character of every tile (`_` for empty) in row-major order. Boards of different sizes with
+    /// the same tiles hash differently.
+    pub fn fingerprint(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        std::iter::once(S as u8)
+            .chain(self.tiles.iter().map(|tile| tile.to_char() as u8))
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            })
+    }
+
     pub fn tiles(&self) -> std::slice::Iter<'_, Tile> {
         self.tiles.iter()
     }
+    /// Each row of tiles, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[Tile]> {
+        let mut rest = self.tiles.as_slice();
+        (0..row_count::<S>()).map(move |row| {
+            let (tiles, next) = rest.split_at(row_length::<S>(row));
+            rest = next;
+            tiles
+        })
+    }
+    /// Each row of tiles, top to bottom, paired with their coordinates.
+    pub fn rows_enumerated(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = (BoardCoord, &Tile)>> {
+        self.rows().enumerate().map(|(row, tiles)| {
+            tiles
+                .iter()
+                .enumerate()
+                .map(move |(col, tile)| (BoardCoord::new(row, col), tile))
+        })
+    }
     pub fn nonempty_tiles(&self) -> impl Iterator<Item = (BoardCoord, &Tile)> {
         self.tiles.iter().enumerate().filter_map(|(idx, tile)| {
             if *tile == Tile::Empty {
@@ -64,109 +256,63 @@ where
         })
     }
 
+    /// The tile next to `coord` in `direction`, or empty past the edge of the board.
+    pub fn get_neighbor(&self, coord: &BoardCoord, direction: HexDirection) -> &Tile {
+        coord
+            .neighbor::<S>(direction)
+            .map_or(&Tile::Empty, |neighbor| self.get_tile(neighbor))
+    }
     pub fn get_upper_left_neighbor(&self, coord: &BoardCoord) -> &Tile {
-        let is_upper_half = coord.row < S;
-        if coord.row == 0 {
-            &Tile::Empty
-        } else if is_upper_half {
-            if coord.col == 0 {
-                &Tile::Empty
-            } else {
-                self.get_tile(&BoardCoord {
-                    row: coord.row - 1,
-                    col: coord.col - 1,
-                })
-            }
-        } else {
-            self.get_tile(&BoardCoord {
-                row: coord.row - 1,
-                col: coord.col,
-            })
-        }
+        self.get_neighbor(coord, HexDirection::UpperLeft)
     }
     pub fn get_upper_right_neighbor(&self, coord: &BoardCoord) -> &Tile {
-        let is_upper_half = coord.row < S;
-        let upper_col_index = if is_upper_half {
-            coord.col
-        } else {
-            coord.col + 1
-        };
-
-        if coord.row == 0 || upper_col_index >= row_length::<S>(coord.row - 1) {
-            &Tile::Empty
-        } else {
-            self.get_tile(&BoardCoord {
-                row: coord.row - 1,
-                col: upper_col_index,
-            })
-        }
+        self.get_neighbor(coord, HexDirection::UpperRight)
     }
     pub fn get_left_neighbor(&self, coord: &BoardCoord) -> &Tile {
-        if coord.col == 0 {
-            &Tile::Empty
-        } else {
-            self.get_tile(&BoardCoord {
-                row: coord.row,
-                col: coord.col - 1,
-            })
-        }
+        self.get_neighbor(coord, HexDirection::Left)
     }
     pub fn get_right_neighbor(&self, coord: &BoardCoord) -> &Tile {
-        if coord.col >= row_length::<S>(coord.row) - 1 {
-            &Tile::Empty
-        } else {
-            self.get_tile(&BoardCoord {
-                row: coord.row,
-                col: coord.col + 1,
-            })
-        }
+        self.get_neighbor(coord, HexDirection::Right)
     }
     pub fn get_lower_left_neighbor(&self, coord: &BoardCoord) -> &Tile {
-        let is_lower_half = coord.row >= S - 1;
-        if coord.row == row_count::<S>() - 1 {
-            &Tile::Empty
-        } else if is_lower_half {
-            if coord.col == 0 {
-                &Tile::Empty
-            } else {
-                self.get_tile(&BoardCoord {
-                    row: coord.row + 1,
-                    col: coord.col - 1,
-                })
-            }
-        } else {
-            self.get_tile(&BoardCoord {
-                row: coord.row + 1,
-                col: coord.col,
-            })
-        }
+        self.get_neighbor(coord, HexDirection::LowerLeft)
     }
     pub fn get_lower_right_neighbor(&self, coord: &BoardCoord) -> &Tile {
-        let is_lower_half = coord.row >= S - 1;
-        let lower_col_index = if is_lower_half {
-            coord.col
-        } else {
-            coord.col + 1
-        };
+        self.get_neighbor(coord, HexDirection::LowerRight)
+    }
+    pub fn neighbors(&self, coord: &BoardCoord) -> [&Tile; 6] {
+        HexDirection::ALL.map(|direction| self.get_neighbor(coord, direction))
+    }
 
-        if coord.row == row_count::<S>() - 1 || lower_col_index >= row_length::<S>(coord.row + 1) {
-            &Tile::Empty
+    /// The coordinates `k` steps from the center of the board, clockwise from the left corner.
+    /// Ring 0 is the center alone, and rings past the edge of the board are empty.
+    pub fn ring(k: usize) -> impl Iterator<Item = BoardCoord> {
+        let k = k as isize;
+        let mut coords = Vec::new();
+        if k == 0 {
+            coords.extend(BoardCoord::from_axial::<S>(0, 0));
         } else {
-            self.get_tile(&BoardCoord {
-                row: coord.row + 1,
-                col: lower_col_index,
-            })
+            let (mut q, mut r) = (-k, 0);
+            for direction in HexDirection::ALL.into_iter().cycle().skip(1).take(6) {
+                let (dq, dr) = direction.axial_offset();
+                for _ in 0..k {
+                    coords.extend(BoardCoord::from_axial::<S>(q, r));
+                    (q, r) = (q + dq, r + dr);
+                }
+            }
         }
+        coords.into_iter()
     }
-    pub fn neighbors(&self, coord: &BoardCoord) -> [&Tile; 6] {
-        [
-            self.get_upper_left_neighbor(coord),
-            self.get_upper_right_neighbor(coord),
-            self.get_right_neighbor(coord),
-            self.get_lower_right_neighbor(coord),
-            self.get_lower_left_neighbor(coord),
-            self.get_left_neighbor(coord),
-        ]
+    /// Every coordinate on the board, ring by ring from the center outward.
+    pub fn spiral() -> impl Iterator<Item = BoardCoord> {
+        (0..S).flat_map(Self::ring)
+    }
+
+    /// The coordinate in each direction around `coord`, in the same order as [`Board::neighbors`].
+    /// Positions off the board are `None`.
+    pub fn neighbor_coords(&self, coord: &BoardCoord) -> [(HexDirection, Option<BoardCoord>); 6] {
+        let coords = coord.neighbor_coords::<S>();
+        HexDirection::ALL.map(|direction| (direction, coords[direction as usize]))
     }
 
     // Return true if tile at `coord` is selectable (>=3 consecutive empty neighbors)
@@ -198,28 +344,122 @@ where
         self.tiles
             .iter()
             .enumerate()
-            .filter_map(|(idx, tile)| {
-                if *tile == Tile::Empty {
-                    None
-                } else {
-                    let coord = BoardCoord::from_index::<S>(idx);
-                    if self.is_selectable(&coord) {
-                        Some((coord, tile))
-                    } else {
-                        None
-                    }
-                }
-            })
+            .filter(|(idx, _)| self.selectable[*idx])
+            .map(|(idx, tile)| (BoardCoord::from_index::<S>(idx), tile))
             .collect::<HashSet<_>>()
     }
 
+    /// Find every legal move under [`ClassicRules::default`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     pub fn find_match_sets(&self) -> MatchSets {
-        let candidates = self.selectable_tiles();
+        self.find_match_sets_with(&ClassicRules::default())
+    }
+
+    /// Parse a board from hand-edited text, which may have blank lines, comment lines starting
+    /// with `#`, indentation (e.g. to lay the rows out as a hexagon) and trailing whitespace.
+    ///
+    /// Anything the strict parser accepts parses the same way, so a space is still an empty tile;
+    /// only whitespace beyond a row's length is stripped, indentation first.
+    pub fn from_str_lenient(s: &str) -> Result<Self, BoardParseError> {
+        if let Ok(board) = s.parse() {
+            return Ok(board);
+        }
+        let rows: Vec<&str> = s
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .enumerate()
+            .map(|(row, line)| {
+                let Some(length) = (row < row_count::<S>()).then(|| row_length::<S>(row)) else {
+                    return line;
+                };
+                let excess = |line: &str| line.chars().count().saturating_sub(length);
+                let leading = line.len() - line.trim_start().len();
+                let line = &line[leading.min(excess(line))..];
+                let trailing = line.len() - line.trim_end().len();
+                &line[..line.len() - trailing.min(excess(line))]
+            })
+            .collect();
+        rows.join("\n").parse()
+    }
 
-        candidates
+    /// Parse a board typed out as a hexagon: one space between tiles, and each row indented by
+    /// one more space for every tile it is shorter than the middle row, plus any common margin.
+    ///
+    /// Unlike [`Board::from_str_lenient`], tiles are found by their position in the layout, so
+    /// empty tiles may be written as spaces (as well as `_` or `.`), and trailing empty tiles may
+    /// be left off. Blank lines and comment lines starting with `#` are skipped.
+    pub fn from_str_visual(s: &str) -> Result<Self, BoardParseError> {
+        let lines: Vec<&str> = s
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty() && !line.trim_start().starts_with('#'))
+            .collect();
+        if lines.len() != row_count::<S>() {
+            return Err(BoardParseError::InvalidRowCount(
+                row_count::<S>(),
+                lines.len(),
+            ));
+        }
+
+        let shift = |row: usize| (row_count::<S>() - row_length::<S>(row)) as isize;
+        // The margin is the indentation shared by every row, beyond the hexagonal offsets.
+        let margin = lines
             .iter()
-            .flat_map(|(c, t)| t.filter_matches(c, self, candidates.iter().cloned()))
-            .collect::<MatchSets>()
+            .enumerate()
+            .map(|(row, line)| (line.len() - line.trim_start().len()) as isize - shift(row))
+            .min()
+            .unwrap_or(0);
+
+        let mut tiles = [Tile::Empty; board_area::<S>()];
+        for (row, line) in lines.iter().enumerate() {
+            for (pos, c) in line.chars().enumerate() {
+                if c.is_whitespace() {
+                    continue;
+                }
+                let offset = pos as isize - margin - shift(row);
+                if offset < 0 || offset % 2 == 1 {
+                    return Err(BoardParseError::MisalignedTile(row, pos));
+                }
+                let col = offset as usize / 2;
+                if col >= row_length::<S>(row) {
+                    return Err(BoardParseError::InvalidRowLength(
+                        row_length::<S>(row),
+                        col + 1,
+                    ));
+                }
+                tiles[BoardCoord::new(row, col).as_index::<S>()] = match c {
+                    '.' => Tile::Empty,
+                    c => Tile::try_from(c)?,
+                };
+            }
+        }
+        Ok(Self::from_tiles(tiles))
+    }
+}
+
+impl MatchSet {
+    /// Check that this is a legal move on `board`: every tile is selectable and the tiles match
+    /// each other. Returns the kind of match it makes.
+    pub fn validate<const S: usize>(&self, board: &Board<S>) -> Result<MoveType, InvalidMoveError>
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        for coord in self.iter() {
+            if !coord.is_on_board::<S>() {
+                return Err(InvalidMoveError::OutOfRange(*coord));
+            }
+            if *board.get_tile(coord) == Tile::Empty {
+                return Err(InvalidMoveError::EmptyTile(*coord));
+            }
+            if !board.selectable[coord.as_index::<S>()] {
+                return Err(InvalidMoveError::NotSelectable(*coord));
+            }
+        }
+        if !board.find_match_sets().contains(self) {
+            let tiles = self.iter().map(|coord| *board.get_tile(coord)).collect();
+            return Err(InvalidMoveError::IllegalMatch(tiles));
+        }
+        Ok(MoveType::identify(board, self))
     }
 }
 
@@ -232,46 +472,126 @@ where
         for (c, t) in iter {
             tile_array[c.as_index::<S>()] = t;
         }
-        Self { tiles: tile_array }
+        Self::from_tiles(tile_array)
     }
 }
 
-impl<const S: usize> FromStr for Board<S>
+/// Builds a board from every tile in row-major order, as returned by [`Board::tiles`].
+impl<const S: usize> TryFrom<&[Tile]> for Board<S>
 where
     [(); board_area::<S>()]: Sized,
 {
-    type Err = BoardParseError;
+    type Error = TileCountError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tiles = [Tile::Empty; board_area::<S>()];
-        let line_count = s.matches("\n").count() + 1;
-        if line_count != row_count::<S>() {
-            return Err(BoardParseError::InvalidRowCount(
-                row_count::<S>(),
-                line_count,
-            ));
+    fn try_from(tiles: &[Tile]) -> Result<Self, Self::Error> {
+        let tiles = tiles.try_into().map_err(|_| TileCountError {
+            expected: board_area::<S>(),
+            found: tiles.len(),
+        })?;
+        Ok(Self::from_tiles(tiles))
+    }
+}
+impl<const S: usize> TryFrom<Vec<Tile>> for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    type Error = TileCountError;
+
+    fn try_from(tiles: Vec<Tile>) -> Result<Self, Self::Error> {
+        Self::try_from(tiles.as_slice())
+    }
+}
+
+/// One non-empty tile in the JSON form of a board or solution.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct TileEntry {
+    pub(crate) row: usize,
+    pub(crate) col: usize,
+    pub(crate) tile: Tile,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardRepr {
+    size: usize,
+    tiles: Vec<TileEntry>,
+}
+
+/// Serialized as an object with the board `size` and a `tiles` array of every non-empty tile:
+///
+/// ```json
+/// { "size": 6, "tiles": [{ "row": 0, "col": 2, "tile": "vitae" }, ...] }
+/// ```
+///
+/// Deserializing rejects a different size, tiles off the board, and tiles given twice.
+#[cfg(feature = "serde")]
+impl<const S: usize> serde::Serialize for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        BoardRepr {
+            size: S,
+            tiles: self
+                .nonempty_tiles()
+                .map(|(coord, tile)| TileEntry {
+                    row: coord.row,
+                    col: coord.col,
+                    tile: *tile,
+                })
+                .collect(),
         }
+        .serialize(serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, const S: usize> serde::Deserialize<'de> for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
 
-        for (row_idx, line) in s.lines().enumerate() {
-            if row_length::<S>(row_idx) != line.len() {
-                return Err(BoardParseError::InvalidRowLength(
-                    row_length::<S>(row_idx),
-                    line.len(),
-                ));
+        let repr = BoardRepr::deserialize(deserializer)?;
+        if repr.size != S {
+            return Err(D::Error::custom(format!(
+                "expected a board of size {}, found {}",
+                S, repr.size
+            )));
+        }
+        let mut tiles = [Tile::Empty; board_area::<S>()];
+        for entry in repr.tiles {
+            let coord = BoardCoord::new(entry.row, entry.col);
+            if !coord.is_on_board::<S>() {
+                return Err(D::Error::custom(format!("{:?} is off the board", coord)));
             }
-
-            for (col_idx, c) in line.chars().enumerate() {
-                let tile = Tile::try_from(c)?;
-                tiles[BoardCoord::new(row_idx, col_idx).as_index::<S>()] = tile;
+            let idx = coord.as_index::<S>();
+            if tiles[idx] != Tile::Empty {
+                return Err(D::Error::custom(format!("{:?} is given twice", coord)));
             }
+            tiles[idx] = entry.tile;
         }
+        Ok(Self::from_tiles(tiles))
+    }
+}
+
+impl<const S: usize> FromStr for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    type Err = BoardParseError;
 
-        Ok(Self { tiles })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &TileAlphabet::default())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
     use crate::tile::ElementTile;
 
     use super::*;
@@ -353,4 +673,319 @@ mod tests {
 
         assert!(match_sets.is_empty());
     }
+
+    #[test]
+    fn test_axial_tile_access() {
+        let mut board = Board::<3>::empty();
+        let center = crate::AxialCoord::new(0, 0);
+        board.set_tile(center, Tile::Gold);
+        board.set_tile(crate::AxialCoord::new(1, -2), Tile::Theta);
+        assert_eq!(board.get_tile(BoardCoord::new(2, 2)), &Tile::Gold);
+        assert_eq!(board.get_tile(BoardCoord::new(0, 1)), &Tile::Theta);
+        board.remove_tile(center);
+        assert_eq!(board.get_tile(center), &Tile::Empty);
+        assert_eq!(board.census().total(), 1);
+    }
+
+    #[test]
+    fn test_match_sets_are_ordered() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 4), Tile::Theta),
+        ]);
+        let match_sets: Vec<MatchSet> = board.find_match_sets().into_iter().collect();
+        assert_eq!(
+            match_sets,
+            vec![
+                MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(2, 4)]),
+                MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(4, 2)]),
+                MatchSet::from([BoardCoord::new(2, 4), BoardCoord::new(4, 2)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_neighbor_coords_match_neighbors() {
+        let mut board = Board::<3>::empty();
+        for idx in 0..board_area::<3>() {
+            board.set_tile(BoardCoord::from_index::<3>(idx), Tile::Gold);
+        }
+        for idx in 0..board_area::<3>() {
+            let coord = BoardCoord::from_index::<3>(idx);
+            let tiles = board.neighbors(&coord);
+            for (i, (direction, neighbor)) in board.neighbor_coords(&coord).into_iter().enumerate()
+            {
+                assert_eq!(direction, HexDirection::ALL[i]);
+                assert_eq!(
+                    neighbor.map_or(Tile::Empty, |n| *board.get_tile(n)),
+                    *tiles[i]
+                );
+            }
+        }
+        assert_eq!(
+            board.neighbor_coords(&BoardCoord::new(0, 0))[2],
+            (HexDirection::Right, Some(BoardCoord::new(0, 1)))
+        );
+    }
+
+    #[test]
+    fn test_selectable_cache_tracks_mutations() {
+        fn uncached(board: &Board<3>) -> HashSet<(BoardCoord, &Tile)> {
+            board
+                .nonempty_tiles()
+                .filter(|(coord, _)| board.is_selectable(coord))
+                .collect()
+        }
+        let mut board = Board::<3>::from_tiles([Tile::Gold; board_area::<3>()]);
+        assert_eq!(board.selectable_tiles(), uncached(&board));
+
+        for idx in [0, 4, 9, 10, 14] {
+            board.remove_tile(BoardCoord::from_index::<3>(idx));
+            assert_eq!(board.selectable_tiles(), uncached(&board));
+        }
+        board.set_tile(BoardCoord::new(2, 2), Tile::Theta);
+        assert_eq!(board.selectable_tiles(), uncached(&board));
+    }
+
+    #[test]
+    fn test_counters_track_mutations() {
+        let mut board = Board::<3>::from_tiles([Tile::Gold; board_area::<3>()]);
+        assert_eq!(board.remaining(), 19);
+        assert_eq!(board.free_count(), 6);
+
+        board.remove_tile(BoardCoord::new(2, 2));
+        board.remove_tile(BoardCoord::new(2, 2));
+        assert_eq!(board.remaining(), 18);
+        board.set_tile(BoardCoord::new(0, 0), Tile::Theta);
+        assert_eq!(board.remaining(), 18);
+
+        for idx in 0..board_area::<3>() {
+            board.remove_tile(BoardCoord::from_index::<3>(idx));
+            assert_eq!(board.remaining(), board.nonempty_tiles().count());
+            assert_eq!(board.free_count(), board.selectable_tiles().len());
+        }
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn test_apply_match_checks_legality() {
+        let mut board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 2), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(4, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 2), Tile::Gold),
+            (BoardCoord::new(2, 1), Tile::Theta),
+            (BoardCoord::new(2, 3), Tile::Theta),
+            (BoardCoord::new(1, 1), Tile::Theta),
+            (BoardCoord::new(3, 2), Tile::Theta),
+        ]);
+        let before = board.clone();
+
+        assert_eq!(
+            board.apply_match(&MatchSet::from([
+                BoardCoord::new(0, 0),
+                BoardCoord::new(0, 2)
+            ])),
+            Err(InvalidMoveError::IllegalMatch(vec![
+                Tile::Element(ElementTile::Fire),
+                Tile::Element(ElementTile::Water)
+            ]))
+        );
+        assert_eq!(
+            board.apply_match(&MatchSet::from([BoardCoord::new(2, 2)])),
+            Err(InvalidMoveError::NotSelectable(BoardCoord::new(2, 2)))
+        );
+        assert_eq!(
+            board.apply_match(&MatchSet::from([BoardCoord::new(0, 1)])),
+            Err(InvalidMoveError::EmptyTile(BoardCoord::new(0, 1)))
+        );
+        assert_eq!(
+            board.apply_match(&MatchSet::from([BoardCoord::new(5, 0)])),
+            Err(InvalidMoveError::OutOfRange(BoardCoord::new(5, 0)))
+        );
+        assert_eq!(board, before);
+
+        assert_eq!(
+            MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(4, 0)]).validate(&board),
+            Ok(MoveType::Element)
+        );
+        assert_eq!(
+            MatchSet::from([BoardCoord::new(2, 1), BoardCoord::new(2, 3)]).validate(&board),
+            Ok(MoveType::ThetaTheta)
+        );
+        assert_eq!(
+            board.apply_match(&MatchSet::from([
+                BoardCoord::new(0, 0),
+                BoardCoord::new(4, 0)
+            ])),
+            Ok(())
+        );
+        assert_eq!(board.get_tile(BoardCoord::new(0, 0)), &Tile::Empty);
+    }
+
+    #[test]
+    fn test_fallible_tile_access() {
+        let mut board = Board::<3>::empty();
+        assert_eq!(
+            board.try_set_tile(&BoardCoord::new(4, 2), Tile::Gold),
+            Ok(())
+        );
+        assert_eq!(board.try_get_tile(&BoardCoord::new(4, 2)), Ok(&Tile::Gold));
+
+        let off_board = BoardCoord::new(4, 3);
+        assert!(Board::<3>::contains(&BoardCoord::new(4, 2)));
+        assert!(!Board::<3>::contains(&off_board));
+        assert_eq!(
+            board.try_get_tile(&off_board),
+            Err(CoordError::OffBoard(off_board, 3))
+        );
+        assert_eq!(
+            board.try_set_tile(&BoardCoord::new(9, 0), Tile::Gold),
+            Err(CoordError::OffBoard(BoardCoord::new(9, 0), 3))
+        );
+    }
+
+    #[test]
+    fn test_overlay() {
+        let metals = Board::<3>::from_iter([(BoardCoord::new(2, 2), Tile::Gold)]);
+        let elements = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        ]);
+        let merged = metals.overlay(&elements).unwrap();
+        assert_eq!(merged.nonempty_tiles().count(), 3);
+        assert_eq!(merged.overlay(&metals), Ok(merged.clone()));
+
+        let clash = Board::<3>::from_iter([(BoardCoord::new(2, 2), Tile::Theta)]);
+        assert_eq!(
+            merged.overlay(&clash),
+            Err(OverlapError {
+                coord: BoardCoord::new(2, 2),
+                ours: Tile::Gold,
+                theirs: Tile::Theta
+            })
+        );
+    }
+
+    #[test]
+    fn test_shuffled_keeps_tiles_and_cells() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(1, 2), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(2, 2), Tile::Gold),
+            (BoardCoord::new(4, 1), Tile::Theta),
+        ]);
+        let mut rng = StdRng::seed_from_u64(7);
+        let sorted_tiles = |board: &Board<3>| {
+            let mut tiles: Vec<Tile> = board.nonempty_tiles().map(|(_, t)| *t).collect();
+            tiles.sort();
+            tiles
+        };
+        let cells = |board: &Board<3>| -> Vec<BoardCoord> {
+            board.nonempty_tiles().map(|(c, _)| c).collect()
+        };
+        for _ in 0..10 {
+            let shuffled = board.shuffled(&mut rng);
+            assert_eq!(sorted_tiles(&shuffled), sorted_tiles(&board));
+            assert_eq!(cells(&shuffled), cells(&board));
+        }
+        assert!((0..10).any(|_| board.shuffled(&mut rng) != board));
+    }
+
+    #[test]
+    fn test_from_str_visual() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 4), Tile::Gold),
+            (BoardCoord::new(4, 1), Tile::Theta),
+        ]);
+        let text = "\n    F\n\n   _ . _ _\n  . . . . 5\n     _\n    . T\n";
+        assert_eq!(Board::<3>::from_str_visual(text), Ok(board));
+
+        assert_eq!(
+            Board::<3>::from_str_visual("  . . .\n  . . . .\n. . . . .\n . . . .\n  . . ."),
+            Err(BoardParseError::MisalignedTile(1, 2))
+        );
+        assert_eq!(
+            Board::<3>::from_str_visual("  . . . F\n . . . .\n. . . . .\n . . . .\n  . . ."),
+            Err(BoardParseError::InvalidRowLength(3, 4))
+        );
+    }
+
+    #[test]
+    fn test_try_from_tile_slices() {
+        let mut tiles = vec![Tile::Empty; board_area::<3>()];
+        tiles[9] = Tile::Gold;
+        let board = Board::<3>::try_from(tiles.clone()).unwrap();
+        assert_eq!(board.get_tile(BoardCoord::new(2, 2)), &Tile::Gold);
+        assert_eq!(Board::<3>::try_from(board.tiles().as_slice()), Ok(board));
+
+        tiles.pop();
+        assert_eq!(
+            Board::<3>::try_from(tiles),
+            Err(TileCountError {
+                expected: 19,
+                found: 18
+            })
+        );
+    }
+
+    #[test]
+    fn test_rows() {
+        let board = Board::<3>::from_iter([(BoardCoord::new(4, 2), Tile::Gold)]);
+        assert_eq!(
+            board.rows().map(<[Tile]>::len).collect::<Vec<_>>(),
+            [3, 4, 5, 4, 3]
+        );
+        assert_eq!(board.rows().last().unwrap()[2], Tile::Gold);
+
+        let coords: Vec<BoardCoord> = board
+            .rows_enumerated()
+            .flat_map(|row| row.map(|(coord, _)| coord))
+            .collect();
+        assert_eq!(
+            coords,
+            (0..board_area::<3>())
+                .map(BoardCoord::from_index::<3>)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // Pinned so that changing the hash shows up as a test failure.
+        assert_eq!(Board::<3>::empty().fingerprint(), 11890700728341767253);
+        let board = Board::<3>::from_iter([(BoardCoord::new(2, 2), Tile::Gold)]);
+        assert_eq!(board.fingerprint(), 10063162095926296091);
+        assert_ne!(
+            Board::<2>::empty().fingerprint(),
+            Board::<3>::empty().fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_rings_and_spiral() {
+        assert_eq!(
+            Board::<3>::ring(0).collect::<Vec<_>>(),
+            [BoardCoord::new(2, 2)]
+        );
+        assert_eq!(
+            Board::<3>::ring(1).collect::<Vec<_>>(),
+            [
+                BoardCoord::new(2, 1),
+                BoardCoord::new(1, 1),
+                BoardCoord::new(1, 2),
+                BoardCoord::new(2, 3),
+                BoardCoord::new(3, 2),
+                BoardCoord::new(3, 1),
+            ]
+        );
+        assert_eq!(Board::<3>::ring(2).count(), 12);
+        assert_eq!(Board::<3>::ring(3).count(), 0);
+
+        let spiral: HashSet<BoardCoord> = Board::<6>::spiral().collect();
+        assert_eq!(spiral.len(), board_area::<6>());
+        assert_eq!(Board::<6>::spiral().count(), board_area::<6>());
+    }
 }
@@ -0,0 +1,127 @@
+use crate::board::Board;
+use crate::coord::BoardCoord;
+use crate::errors::BoardBuildError;
+use crate::invariants::STANDARD_TILE_COUNTS;
+use crate::math::board_area;
+use crate::tile::Tile;
+
+/// Builds a [`Board`] one tile at a time, rejecting placements that couldn't happen in the real game.
+///
+/// Unlike collecting into a `Board`, which silently overwrites, each placement is checked against the
+/// tiles already placed and the standard tile counts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardBuilder<const S: usize>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    board: Board<S>,
+}
+
+impl<const S: usize> BoardBuilder<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    pub fn new() -> Self {
+        Self {
+            board: Board::empty(),
+        }
+    }
+
+    /// Place `tile` at `coord`, or report why it can't go there.
+    pub fn place(&mut self, coord: BoardCoord, tile: Tile) -> Result<&mut Self, BoardBuildError> {
+        if !coord.is_on_board::<S>() {
+            return Err(BoardBuildError::OutOfRange(coord));
+        }
+        let existing = *self.board.get_tile(coord);
+        if existing != Tile::Empty {
+            return Err(BoardBuildError::Occupied(coord, existing));
+        }
+
+        let count = self.board.census().count(tile);
+        let limit = STANDARD_TILE_COUNTS
+            .iter()
+            .find(|(standard, _)| *standard == tile)
+            .map_or(usize::MAX, |(_, limit)| *limit);
+        if count >= limit {
+            return Err(match tile {
+                Tile::Gold => BoardBuildError::DuplicateGold,
+                Tile::Metal(metal) => BoardBuildError::DuplicateMetal(metal),
+                _ => BoardBuildError::TooMany { tile, limit },
+            });
+        }
+
+        self.board.set_tile(coord, tile);
+        Ok(self)
+    }
+
+    /// Place every tile from `tiles`, stopping at the first that can't be placed.
+    pub fn place_all(
+        &mut self,
+        tiles: impl IntoIterator<Item = (BoardCoord, Tile)>,
+    ) -> Result<&mut Self, BoardBuildError> {
+        for (coord, tile) in tiles {
+            self.place(coord, tile)?;
+        }
+        Ok(self)
+    }
+
+    pub fn build(&self) -> Board<S> {
+        self.board.clone()
+    }
+}
+
+impl<const S: usize> Default for BoardBuilder<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::{ElementTile, MetalTile};
+
+    #[test]
+    fn test_builder_rejects_bad_placements() {
+        let mut builder = BoardBuilder::<3>::new();
+        builder
+            .place(BoardCoord::new(0, 0), Tile::Gold)
+            .unwrap()
+            .place(BoardCoord::new(0, 1), Tile::Metal(MetalTile::Lead))
+            .unwrap();
+
+        assert_eq!(
+            builder.place(BoardCoord::new(0, 3), Tile::Theta).err(),
+            Some(BoardBuildError::OutOfRange(BoardCoord::new(0, 3)))
+        );
+        assert_eq!(
+            builder.place(BoardCoord::new(0, 0), Tile::Theta).err(),
+            Some(BoardBuildError::Occupied(BoardCoord::new(0, 0), Tile::Gold))
+        );
+        assert_eq!(
+            builder.place(BoardCoord::new(1, 0), Tile::Gold).err(),
+            Some(BoardBuildError::DuplicateGold)
+        );
+        assert_eq!(
+            builder
+                .place(BoardCoord::new(1, 0), Tile::Metal(MetalTile::Lead))
+                .err(),
+            Some(BoardBuildError::DuplicateMetal(MetalTile::Lead))
+        );
+
+        let fire = Tile::Element(ElementTile::Fire);
+        let result =
+            builder.place_all((0..9).map(|idx| (BoardCoord::from_index::<3>(idx + 2), fire)));
+        assert_eq!(
+            result.err(),
+            Some(BoardBuildError::TooMany {
+                tile: fire,
+                limit: 8
+            })
+        );
+        assert_eq!(builder.build().census().count(fire), 8);
+    }
+}
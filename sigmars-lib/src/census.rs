@@ -0,0 +1,178 @@
+use crate::board::Board;
+use crate::math::board_area;
+use crate::tile::{BinaryTile, ElementTile, MetalTile, Tile};
+
+/// How many of each kind of tile are on a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TileCensus {
+    /// Indexed by [`ElementTile`] discriminant.
+    elements: [usize; 4],
+    /// Indexed by [`MetalTile`] discriminant.
+    metals: [usize; 5],
+    pub theta: usize,
+    pub life: usize,
+    pub death: usize,
+    pub quicksilver: usize,
+    pub gold: usize,
+    #[cfg(feature = "quintessence")]
+    pub quintessence: usize,
+    pub blocked: usize,
+    pub empty: usize,
+}
+impl TileCensus {
+    /// Number of `tile` on the board.
+    pub fn count(&self, tile: Tile) -> usize {
+        match tile {
+            Tile::Empty => self.empty,
+            Tile::Theta => self.theta,
+            Tile::Element(element) => self.elements[element as usize],
+            Tile::Binary(BinaryTile::Life) => self.life,
+            Tile::Binary(BinaryTile::Death) => self.death,
+            Tile::Quicksilver => self.quicksilver,
+            Tile::Metal(metal) => self.metals[metal as usize],
+            Tile::Gold => self.gold,
+            Tile::Blocked => self.blocked,
+            #[cfg(feature = "quintessence")]
+            Tile::Quintessence => self.quintessence,
+        }
+    }
+    pub fn elements_remaining(&self, element: ElementTile) -> usize {
+        self.elements[element as usize]
+    }
+    /// Number of metals on the board, not counting gold.
+    pub fn metals_remaining(&self) -> usize {
+        self.metals.iter().sum()
+    }
+    pub fn has_metal(&self, metal: MetalTile) -> bool {
+        self.metals[metal as usize] > 0
+    }
+    /// Number of tiles on the board which need clearing, not counting empty or blocked cells.
+    pub fn total(&self) -> usize {
+        let total = self.elements.iter().sum::<usize>()
+            + self.metals_remaining()
+            + self.theta
+            + self.life
+            + self.death
+            + self.quicksilver
+            + self.gold;
+        #[cfg(feature = "quintessence")]
+        let total = total + self.quintessence;
+        total
+    }
+}
+
+/// Where a board is in the metal sequence: quicksilver projects the lowest metal left, then the
+/// next, and gold comes last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetalProgression {
+    /// Whether each metal is on the board, indexed by [`MetalTile`] discriminant.
+    remaining: [bool; 5],
+    gold: bool,
+}
+impl MetalProgression {
+    pub fn from_census(census: &TileCensus) -> Self {
+        Self {
+            remaining: MetalTile::ALL.map(|metal| census.has_metal(metal)),
+            gold: census.gold > 0,
+        }
+    }
+
+    /// The metal quicksilver can project now, if any are left.
+    pub fn projectable(&self) -> Option<MetalTile> {
+        MetalTile::ALL
+            .into_iter()
+            .find(|metal| self.remaining[*metal as usize])
+    }
+    /// The metals still waiting on a lower metal, lowest first.
+    pub fn locked(&self) -> impl Iterator<Item = MetalTile> + '_ {
+        MetalTile::ALL
+            .into_iter()
+            .filter(|metal| self.remaining[*metal as usize])
+            .skip(1)
+    }
+    pub fn is_locked(&self, metal: MetalTile) -> bool {
+        self.locked().any(|locked| locked == metal)
+    }
+    /// Whether there is gold on the board and every other metal is gone.
+    pub fn is_gold_unlocked(&self) -> bool {
+        self.gold && self.projectable().is_none()
+    }
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    pub fn metal_progression(&self) -> MetalProgression {
+        MetalProgression::from_census(&self.census())
+    }
+
+    /// Count the tiles on this board by kind.
+    pub fn census(&self) -> TileCensus {
+        let mut census = TileCensus::default();
+        for tile in self.tiles() {
+            match tile {
+                Tile::Empty => census.empty += 1,
+                Tile::Theta => census.theta += 1,
+                Tile::Element(element) => census.elements[*element as usize] += 1,
+                Tile::Binary(BinaryTile::Life) => census.life += 1,
+                Tile::Binary(BinaryTile::Death) => census.death += 1,
+                Tile::Quicksilver => census.quicksilver += 1,
+                Tile::Metal(metal) => census.metals[*metal as usize] += 1,
+                Tile::Gold => census.gold += 1,
+                Tile::Blocked => census.blocked += 1,
+                #[cfg(feature = "quintessence")]
+                Tile::Quintessence => census.quintessence += 1,
+            }
+        }
+        census
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coord::BoardCoord;
+
+    #[test]
+    fn test_census_counts_tiles() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 2), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 0), Tile::Metal(MetalTile::Tin)),
+            (BoardCoord::new(4, 2), Tile::Quicksilver),
+        ]);
+        let census = board.census();
+
+        assert_eq!(census.elements_remaining(ElementTile::Fire), 2);
+        assert_eq!(census.elements_remaining(ElementTile::Water), 0);
+        assert_eq!(census.metals_remaining(), 1);
+        assert!(census.has_metal(MetalTile::Tin));
+        assert_eq!(census.count(Tile::Quicksilver), 1);
+        assert_eq!(census.total(), 4);
+        assert_eq!(census.count(Tile::Empty), board_area::<3>() - 4);
+    }
+
+    #[test]
+    fn test_metal_progression() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Metal(MetalTile::Iron)),
+            (BoardCoord::new(0, 2), Tile::Metal(MetalTile::Tin)),
+            (BoardCoord::new(2, 0), Tile::Metal(MetalTile::Silver)),
+            (BoardCoord::new(2, 2), Tile::Gold),
+        ]);
+        let progression = board.metal_progression();
+        assert_eq!(progression.projectable(), Some(MetalTile::Tin));
+        assert_eq!(
+            progression.locked().collect::<Vec<_>>(),
+            vec![MetalTile::Iron, MetalTile::Silver]
+        );
+        assert!(progression.is_locked(MetalTile::Silver));
+        assert!(!progression.is_locked(MetalTile::Tin));
+        assert!(!progression.is_gold_unlocked());
+
+        let gold_only = Board::<3>::from_iter([(BoardCoord::new(2, 2), Tile::Gold)]);
+        assert_eq!(gold_only.metal_progression().projectable(), None);
+        assert!(gold_only.metal_progression().is_gold_unlocked());
+    }
+}
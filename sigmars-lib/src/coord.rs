@@ -1,9 +1,12 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
-use crate::errors::MatchSetError;
-use crate::math::{board_area, row_count, row_length};
+use smallvec::{SmallVec, smallvec};
+
+use crate::errors::{CoordParseError, MatchSetError};
+use crate::math::{board_area, coord_in_bounds, row_count, row_length};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoardCoord {
     pub row: usize,
     pub col: usize,
@@ -13,6 +16,11 @@ impl BoardCoord {
         Self { row, col }
     }
 
+    /// Whether this coordinate is on a board of size `S`.
+    pub(crate) fn is_on_board<const S: usize>(&self) -> bool {
+        coord_in_bounds::<S>(self.row, self.col)
+    }
+
     pub(crate) fn as_index<const S: usize>(&self) -> usize {
         assert!(self.row < row_count::<S>());
 
@@ -39,25 +47,362 @@ impl BoardCoord {
             }
         }
     }
+
+    /// Convert to axial `(q, r)` coordinates, with the center of the board at the origin.
+    pub(crate) fn to_axial<const S: usize>(self) -> (isize, isize) {
+        let radius = S as isize - 1;
+        let r = self.row as isize - radius;
+        let q_min = if r < 0 { -radius - r } else { -radius };
+        (self.col as isize + q_min, r)
+    }
+
+    /// Convert from axial `(q, r)` coordinates, returning `None` if the position is off the board.
+    pub(crate) fn from_axial<const S: usize>(q: isize, r: isize) -> Option<Self> {
+        let radius = S as isize - 1;
+        if q.abs() > radius || r.abs() > radius || (q + r).abs() > radius {
+            return None;
+        }
+        let q_min = if r < 0 { -radius - r } else { -radius };
+        Some(BoardCoord {
+            row: (r + radius) as usize,
+            col: (q - q_min) as usize,
+        })
+    }
+
+    /// Number of steps between this coordinate and `other` on a board of size `S`.
+    pub fn distance<const S: usize>(&self, other: &BoardCoord) -> usize {
+        let (q1, r1) = self.to_axial::<S>();
+        let (q2, r2) = other.to_axial::<S>();
+        axial_length(q1 - q2, r1 - r2)
+    }
+    /// Whether `other` is one of the six neighbors of this coordinate on a board of size `S`.
+    pub fn is_adjacent<const S: usize>(&self, other: &BoardCoord) -> bool {
+        self.distance::<S>(other) == 1
+    }
+
+    /// The coordinates on the straight line from this coordinate to `other` on a board of size `S`,
+    /// including both ends, with each coordinate adjacent to the one before it.
+    pub fn line_to<const S: usize>(&self, other: &BoardCoord) -> impl Iterator<Item = BoardCoord> {
+        let (q1, r1) = self.to_axial::<S>();
+        let (q2, r2) = other.to_axial::<S>();
+        let steps = self.distance::<S>(other);
+        // Nudge off the midpoints between tiles, so ties always round the same way.
+        let (q1, r1) = (q1 as f64 + 1e-6, r1 as f64 + 1e-6);
+        let (q2, r2) = (q2 as f64 + 1e-6, r2 as f64 + 1e-6);
+        (0..=steps).map(move |i| {
+            let t = if steps == 0 {
+                0.0
+            } else {
+                i as f64 / steps as f64
+            };
+            let (q, r) = round_axial(q1 + (q2 - q1) * t, r1 + (r2 - r1) * t);
+            Self::from_axial::<S>(q, r).expect("lines between tiles stay on the board")
+        })
+    }
+
+    /// Number of steps from the center of a board of size `S` to this coordinate.
+    pub(crate) fn distance_from_center<const S: usize>(self) -> usize {
+        let (q, r) = self.to_axial::<S>();
+        axial_length(q, r)
+    }
+
+    /// The coordinate next to this one in `direction` on a board of size `S`, or `None` past the
+    /// edge of the board.
+    pub fn neighbor<const S: usize>(self, direction: HexDirection) -> Option<Self> {
+        let (dq, dr) = direction.axial_offset();
+        self.offset::<S>(dr, dq)
+    }
+    /// The coordinate `d_row` rows and `d_col` columns away on a board of size `S`, or `None` past
+    /// the edge of the board.
+    ///
+    /// Columns are counted along the board's diagonals rather than from the start of each row, so
+    /// an offset is the same step wherever it starts: `(-1, 0)` is always the upper left neighbor
+    /// and `(1, 0)` the lower right.
+    pub fn offset<const S: usize>(self, d_row: isize, d_col: isize) -> Option<Self> {
+        let (q, r) = self.to_axial::<S>();
+        Self::from_axial::<S>(q + d_col, r + d_row)
+    }
+    /// The coordinates around this one, in the same order as [`Board::neighbors`](crate::Board::neighbors).
+    /// Positions off the board are `None`.
+    pub(crate) fn neighbor_coords<const S: usize>(self) -> [Option<Self>; 6] {
+        HexDirection::ALL.map(|direction| self.neighbor::<S>(direction))
+    }
+}
+
+/// Written as `row,col`, e.g. `3,4`.
+impl std::fmt::Display for BoardCoord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.row, self.col)
+    }
+}
+/// Parses `row,col` (e.g. `3,4`) or `rRcC` (e.g. `r3c4`). This doesn't check the coordinate is
+/// on any particular board.
+impl std::str::FromStr for BoardCoord {
+    type Err = CoordParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CoordParseError(s.to_string());
+        let trimmed = s.trim();
+        let (row, col) = match trimmed.split_once(',') {
+            Some(parts) => parts,
+            None => trimmed
+                .strip_prefix('r')
+                .and_then(|rest| rest.split_once('c'))
+                .ok_or_else(invalid)?,
+        };
+        let row = row.trim().parse().map_err(|_| invalid())?;
+        let col = col.trim().parse().map_err(|_| invalid())?;
+        Ok(BoardCoord::new(row, col))
+    }
+}
+
+/// The six directions from a tile to its neighbors, clockwise from the upper left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexDirection {
+    UpperLeft,
+    UpperRight,
+    Right,
+    LowerRight,
+    LowerLeft,
+    Left,
+}
+impl HexDirection {
+    /// Every direction, in the order [`Board::neighbors`](crate::Board::neighbors) uses.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::UpperLeft,
+        HexDirection::UpperRight,
+        HexDirection::Right,
+        HexDirection::LowerRight,
+        HexDirection::LowerLeft,
+        HexDirection::Left,
+    ];
+
+    /// The change in axial `(q, r)` coordinates when stepping in this direction.
+    pub(crate) fn axial_offset(self) -> (isize, isize) {
+        match self {
+            HexDirection::UpperLeft => (0, -1),
+            HexDirection::UpperRight => (1, -1),
+            HexDirection::Right => (1, 0),
+            HexDirection::LowerRight => (0, 1),
+            HexDirection::LowerLeft => (-1, 1),
+            HexDirection::Left => (-1, 0),
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
-pub struct MatchSet(HashSet<BoardCoord>);
+/// Number of steps from the origin to axial `(q, r)`.
+fn axial_length(q: isize, r: isize) -> usize {
+    q.unsigned_abs()
+        .max(r.unsigned_abs())
+        .max((q + r).unsigned_abs())
+}
+
+/// Round fractional axial coordinates to the nearest tile.
+fn round_axial(q: f64, r: f64) -> (isize, isize) {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    // The rounded coordinates must still sum to zero, so recompute the one which moved most.
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    (rq as isize, rr as isize)
+}
+
+/// A coordinate on a board of size `S`, used by methods like [`Board::get_tile`](crate::Board::get_tile)
+/// so they can take any coordinate type.
+pub trait IntoBoardCoord {
+    /// Convert to a [`BoardCoord`], panicking if the position is off a board of size `S`.
+    fn into_board_coord<const S: usize>(self) -> BoardCoord;
+}
+impl IntoBoardCoord for BoardCoord {
+    fn into_board_coord<const S: usize>(self) -> BoardCoord {
+        self
+    }
+}
+impl<T: IntoBoardCoord + Copy> IntoBoardCoord for &T {
+    fn into_board_coord<const S: usize>(self) -> BoardCoord {
+        (*self).into_board_coord::<S>()
+    }
+}
+
+/// Axial hex coordinates, with the center of the board at the origin.
+///
+/// `q` increases to the right along a row, and `r` increases down the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AxialCoord {
+    pub q: isize,
+    pub r: isize,
+}
+impl AxialCoord {
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+    pub fn from_board_coord<const S: usize>(coord: BoardCoord) -> Self {
+        let (q, r) = coord.to_axial::<S>();
+        Self { q, r }
+    }
+    /// Convert to a [`BoardCoord`], returning `None` if the position is off a board of size `S`.
+    pub fn to_board_coord<const S: usize>(self) -> Option<BoardCoord> {
+        BoardCoord::from_axial::<S>(self.q, self.r)
+    }
+}
+impl IntoBoardCoord for AxialCoord {
+    fn into_board_coord<const S: usize>(self) -> BoardCoord {
+        self.to_board_coord::<S>()
+            .unwrap_or_else(|| panic!("{:?} is off a board of size {}", self, S))
+    }
+}
+impl From<CubeCoord> for AxialCoord {
+    fn from(cube: CubeCoord) -> Self {
+        Self {
+            q: cube.q,
+            r: cube.r,
+        }
+    }
+}
+
+/// Cube hex coordinates, with the center of the board at the origin. `q + r + s` is always 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubeCoord {
+    pub q: isize,
+    pub r: isize,
+    pub s: isize,
+}
+impl CubeCoord {
+    /// Create a cube coordinate, returning `None` unless `q + r + s == 0`.
+    pub const fn new(q: isize, r: isize, s: isize) -> Option<Self> {
+        if q + r + s == 0 {
+            Some(Self { q, r, s })
+        } else {
+            None
+        }
+    }
+    pub fn from_board_coord<const S: usize>(coord: BoardCoord) -> Self {
+        AxialCoord::from_board_coord::<S>(coord).into()
+    }
+    /// Convert to a [`BoardCoord`], returning `None` if the position is off a board of size `S`.
+    pub fn to_board_coord<const S: usize>(self) -> Option<BoardCoord> {
+        AxialCoord::from(self).to_board_coord::<S>()
+    }
+}
+impl IntoBoardCoord for CubeCoord {
+    fn into_board_coord<const S: usize>(self) -> BoardCoord {
+        AxialCoord::from(self).into_board_coord::<S>()
+    }
+}
+impl From<AxialCoord> for CubeCoord {
+    fn from(axial: AxialCoord) -> Self {
+        Self {
+            q: axial.q,
+            r: axial.r,
+            s: -axial.q - axial.r,
+        }
+    }
+}
+
+/// A position given by its ring around the center of the board and its place on that ring, read
+/// like a clock: position 0 is at 12 o'clock (or just past it, on odd rings), counting clockwise.
+///
+/// Written as `ring:position`, e.g. `0:0` for the center or `2:3` for the tile at 3 o'clock two
+/// steps out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClockCoord {
+    pub ring: usize,
+    pub position: usize,
+}
+impl ClockCoord {
+    pub const fn new(ring: usize, position: usize) -> Self {
+        Self { ring, position }
+    }
+    pub fn from_board_coord<const S: usize>(coord: BoardCoord) -> Self {
+        let ring = coord.distance_from_center::<S>();
+        let position = clock_ring(ring)
+            .iter()
+            .position(|&axial| axial == coord.to_axial::<S>())
+            .expect("every coordinate lies on its own ring");
+        Self { ring, position }
+    }
+    /// The coordinate on a board of size `S`, or `None` if the ring or position doesn't exist.
+    pub fn to_board_coord<const S: usize>(self) -> Option<BoardCoord> {
+        let (q, r) = *clock_ring(self.ring).get(self.position)?;
+        BoardCoord::from_axial::<S>(q, r)
+    }
+}
+impl IntoBoardCoord for ClockCoord {
+    fn into_board_coord<const S: usize>(self) -> BoardCoord {
+        self.to_board_coord::<S>()
+            .expect("clock coordinate is off the board")
+    }
+}
+impl std::fmt::Display for ClockCoord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.ring, self.position)
+    }
+}
+
+/// The axial coordinates `k` steps from the center, in [`ClockCoord`] order.
+fn clock_ring(k: usize) -> Vec<(isize, isize)> {
+    if k == 0 {
+        return vec![(0, 0)];
+    }
+    // Walk clockwise from the upper left corner, then rotate to start at 12 o'clock.
+    let (mut q, mut r) = (0, -(k as isize));
+    let mut ring = Vec::with_capacity(6 * k);
+    for direction in HexDirection::ALL.into_iter().cycle().skip(2).take(6) {
+        let (dq, dr) = direction.axial_offset();
+        for _ in 0..k {
+            ring.push((q, r));
+            (q, r) = (q + dq, r + dr);
+        }
+    }
+    ring.rotate_left(k.div_ceil(2));
+    ring
+}
+
+/// The coordinates of a move, kept sorted and deduplicated in an inline buffer, since a match is
+/// only ever a few tiles.
+///
+/// Match sets are ordered by their coordinates in ascending order, compared lexicographically.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct MatchSet(SmallVec<[BoardCoord; 4]>);
 impl MatchSet {
     pub fn from<const N: usize>(coords: [BoardCoord; N]) -> Self {
         assert!(N > 0, "MatchSet must contain at least one item");
-        Self(coords.into())
+        Self::from_coords(coords.into_iter().collect())
+    }
+    /// A move clearing a single tile.
+    pub fn single(coord: BoardCoord) -> Self {
+        Self(smallvec![coord])
+    }
+    /// A move clearing two tiles, which must be different.
+    pub fn pair(a: BoardCoord, b: BoardCoord) -> Result<Self, MatchSetError> {
+        if a == b {
+            return Err(MatchSetError::DuplicateCoord(a));
+        }
+        Ok(Self::from_coords(smallvec![a, b]))
     }
     pub fn try_from_iter<T: IntoIterator<Item = BoardCoord>>(
         iter: T,
     ) -> Result<Self, MatchSetError> {
-        let set = HashSet::from_iter(iter);
-        if set.is_empty() {
+        let set = Self::from_coords(iter.into_iter().collect());
+        if set.0.is_empty() {
             Err(MatchSetError::EmptyMatchSet)
         } else {
-            Ok(Self(set))
+            Ok(set)
         }
     }
+    fn from_coords(mut coords: SmallVec<[BoardCoord; 4]>) -> Self {
+        coords.sort_unstable();
+        coords.dedup();
+        Self(coords)
+    }
 
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
@@ -65,27 +410,287 @@ impl MatchSet {
     }
 
     pub fn contains(&self, coord: &BoardCoord) -> bool {
-        self.0.contains(coord)
+        self.0.binary_search(coord).is_ok()
     }
-    pub fn iter(&self) -> impl Iterator<Item = &BoardCoord> {
+    /// The coordinates in ascending order.
+    pub fn iter(&self) -> std::slice::Iter<'_, BoardCoord> {
         self.0.iter()
     }
+
+    /// Whether the two sets share any coordinate, so playing one removes a tile the other needs.
+    pub fn overlaps(&self, other: &MatchSet) -> bool {
+        self.iter().any(|coord| other.contains(coord))
+    }
+    pub fn is_disjoint(&self, other: &MatchSet) -> bool {
+        !self.overlaps(other)
+    }
+    /// Every coordinate in either set.
+    pub fn union(&self, other: &MatchSet) -> MatchSet {
+        Self::from_coords(self.iter().chain(other.iter()).copied().collect())
+    }
+}
+/// Written with its coordinates in ascending order, e.g. `{(0,2)+(2,7)}`.
+impl std::fmt::Display for MatchSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coords: Vec<String> = self.iter().map(|c| format!("({c})")).collect();
+        write!(f, "{{{}}}", coords.join("+"))
+    }
 }
 impl IntoIterator for MatchSet {
     type Item = BoardCoord;
-    type IntoIter = std::collections::hash_set::IntoIter<BoardCoord>;
+    type IntoIter = smallvec::IntoIter<[BoardCoord; 4]>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
     }
 }
-impl std::hash::Hash for MatchSet {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let mut coords: Vec<&BoardCoord> = self.0.iter().collect();
-        coords.sort();
-        for coord in coords {
-            coord.hash(state);
+/// Serialized as a list of coordinates in ascending order.
+#[cfg(feature = "serde")]
+impl serde::Serialize for MatchSet {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MatchSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let coords = Vec::<BoardCoord>::deserialize(deserializer)?;
+        Self::try_from_iter(coords).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Every move available on a board, iterated in [`MatchSet`] order so enumeration is stable.
+pub type MatchSets = BTreeSet<MatchSet>;
+
+/// Pruning [`MatchSets`] as tiles leave the board.
+pub trait MatchSetsExt {
+    /// Remove every match set using the tile at `coord`, returning the removed sets.
+    fn remove_touching(&mut self, coord: &BoardCoord) -> MatchSets;
+    /// Remove every match set sharing a tile with `match_set`, returning the removed sets. These
+    /// are the moves playing `match_set` makes impossible, including `match_set` itself.
+    fn remove_overlapping(&mut self, match_set: &MatchSet) -> MatchSets;
+}
+impl MatchSetsExt for MatchSets {
+    fn remove_touching(&mut self, coord: &BoardCoord) -> MatchSets {
+        self.extract_if(.., |set| set.contains(coord)).collect()
+    }
+    fn remove_overlapping(&mut self, match_set: &MatchSet) -> MatchSets {
+        self.extract_if(.., |set| set.overlaps(match_set)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_coords_round_trip() {
+        for idx in 0..board_area::<6>() {
+            let coord = BoardCoord::from_index::<6>(idx);
+            let cube = CubeCoord::from_board_coord::<6>(coord);
+            assert_eq!(cube.q + cube.r + cube.s, 0);
+            assert_eq!(cube.to_board_coord::<6>(), Some(coord));
+            assert_eq!(AxialCoord::from(cube).into_board_coord::<6>(), coord);
+        }
+        assert_eq!(
+            AxialCoord::new(0, 0).to_board_coord::<6>(),
+            Some(BoardCoord::new(5, 5))
+        );
+        assert_eq!(AxialCoord::new(3, 3).to_board_coord::<6>(), None);
+    }
+
+    #[test]
+    fn test_clock_coords() {
+        assert_eq!(
+            ClockCoord::from_board_coord::<3>(BoardCoord::new(2, 2)),
+            ClockCoord::new(0, 0)
+        );
+        // Ring 1 starts just past 12 o'clock, at the upper right neighbor of the center.
+        assert_eq!(
+            ClockCoord::new(1, 0).to_board_coord::<3>(),
+            Some(BoardCoord::new(1, 2))
+        );
+        // Ring 2 starts at the top middle tile, and reaches 3 o'clock a quarter of the way round.
+        assert_eq!(
+            ClockCoord::new(2, 0).to_board_coord::<3>(),
+            Some(BoardCoord::new(0, 1))
+        );
+        assert_eq!(
+            ClockCoord::new(2, 3).to_board_coord::<3>(),
+            Some(BoardCoord::new(2, 4))
+        );
+        assert_eq!(ClockCoord::new(2, 12).to_board_coord::<3>(), None);
+        assert_eq!(ClockCoord::new(3, 0).to_board_coord::<3>(), None);
+        assert_eq!(ClockCoord::new(2, 3).to_string(), "2:3");
+
+        for idx in 0..board_area::<6>() {
+            let coord = BoardCoord::from_index::<6>(idx);
+            let clock = ClockCoord::from_board_coord::<6>(coord);
+            assert_eq!(clock.to_board_coord::<6>(), Some(coord));
+        }
+    }
+
+    #[test]
+    fn test_distance_across_staggered_rows() {
+        let center = BoardCoord::new(2, 2);
+        assert_eq!(center.distance::<3>(&BoardCoord::new(0, 0)), 2);
+        assert_eq!(center.distance::<3>(&BoardCoord::new(4, 2)), 2);
+        // Opposite corners.
+        assert_eq!(
+            BoardCoord::new(0, 0).distance::<3>(&BoardCoord::new(4, 2)),
+            4
+        );
+        // Upper and lower neighbors have different columns on either side of the middle row.
+        assert!(BoardCoord::new(1, 1).is_adjacent::<3>(&BoardCoord::new(2, 1)));
+        assert!(BoardCoord::new(1, 1).is_adjacent::<3>(&BoardCoord::new(2, 2)));
+        assert!(BoardCoord::new(3, 1).is_adjacent::<3>(&BoardCoord::new(2, 1)));
+        assert!(BoardCoord::new(3, 1).is_adjacent::<3>(&BoardCoord::new(2, 2)));
+        assert!(!BoardCoord::new(3, 1).is_adjacent::<3>(&BoardCoord::new(2, 0)));
+        assert!(!center.is_adjacent::<3>(&center));
+    }
+
+    #[test]
+    fn test_match_set_display() {
+        let set = MatchSet::from([BoardCoord::new(2, 7), BoardCoord::new(0, 2)]);
+        assert_eq!(set.to_string(), "{(0,2)+(2,7)}");
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![&BoardCoord::new(0, 2), &BoardCoord::new(2, 7)]
+        );
+        assert_eq!(
+            MatchSet::from([BoardCoord::new(1, 1)]).to_string(),
+            "{(1,1)}"
+        );
+    }
+
+    #[test]
+    fn test_match_set_dedups() {
+        let a = BoardCoord::new(3, 1);
+        let b = BoardCoord::new(0, 4);
+        let set = MatchSet::try_from_iter([a, b, a]).unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set, MatchSet::from([b, a]));
+        assert!(set.contains(&a) && !set.contains(&BoardCoord::new(1, 1)));
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![b, a]);
+    }
+
+    #[test]
+    fn test_offset() {
+        // Row 2 is the middle row of a size 3 board; rows above and below are shorter.
+        let center = BoardCoord::new(2, 2);
+        assert_eq!(center.offset::<3>(0, 0), Some(center));
+        assert_eq!(center.offset::<3>(-1, 0), Some(BoardCoord::new(1, 1)));
+        assert_eq!(center.offset::<3>(1, 0), Some(BoardCoord::new(3, 2)));
+        assert_eq!(center.offset::<3>(-2, 2), Some(BoardCoord::new(0, 2)));
+        assert_eq!(BoardCoord::new(0, 0).offset::<3>(-1, 0), None);
+        assert_eq!(BoardCoord::new(4, 2).offset::<3>(0, 1), None);
+        assert_eq!(
+            center.offset::<3>(-1, 1),
+            center.neighbor::<3>(HexDirection::UpperRight)
+        );
+    }
+
+    #[test]
+    fn test_match_set_algebra() {
+        let a = BoardCoord::new(0, 0);
+        let b = BoardCoord::new(1, 1);
+        let c = BoardCoord::new(2, 2);
+        let ab = MatchSet::from([a, b]);
+        let bc = MatchSet::from([b, c]);
+        let single_c = MatchSet::single(c);
+        assert!(ab.overlaps(&bc));
+        assert!(ab.is_disjoint(&single_c));
+        assert_eq!(ab.union(&bc), MatchSet::from([a, b, c]));
+
+        let mut sets = MatchSets::from([ab.clone(), bc.clone(), single_c.clone()]);
+        assert_eq!(sets.remove_touching(&a), MatchSets::from([ab.clone()]));
+        assert_eq!(sets, MatchSets::from([bc.clone(), single_c.clone()]));
+
+        let mut sets = MatchSets::from([ab.clone(), bc.clone(), single_c.clone()]);
+        assert_eq!(
+            sets.remove_overlapping(&single_c),
+            MatchSets::from([bc, single_c])
+        );
+        assert_eq!(sets, MatchSets::from([ab]));
+    }
+
+    #[test]
+    fn test_single_and_pair() {
+        let a = BoardCoord::new(3, 1);
+        let b = BoardCoord::new(0, 4);
+        assert_eq!(MatchSet::single(a), MatchSet::from([a]));
+        assert_eq!(MatchSet::pair(a, b), Ok(MatchSet::from([a, b])));
+        assert_eq!(MatchSet::pair(a, a), Err(MatchSetError::DuplicateCoord(a)));
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let coord = BoardCoord::new(3, 4);
+        assert_eq!(coord.to_string(), "3,4");
+        assert_eq!("3,4".parse(), Ok(coord));
+        assert_eq!(" 3, 4 ".parse(), Ok(coord));
+        assert_eq!("r3c4".parse(), Ok(coord));
+        for bad in ["3", "3,", "r3", "3;4", "-1,2", "rc"] {
+            assert_eq!(
+                bad.parse::<BoardCoord>(),
+                Err(CoordParseError(bad.to_string()))
+            );
         }
     }
+
+    #[test]
+    fn test_neighbor_in_direction() {
+        // Above the middle row, the upper neighbors share or precede this column.
+        let coord = BoardCoord::new(1, 1);
+        assert_eq!(
+            coord.neighbor::<3>(HexDirection::UpperLeft),
+            Some(BoardCoord::new(0, 0))
+        );
+        assert_eq!(
+            coord.neighbor::<3>(HexDirection::LowerRight),
+            Some(BoardCoord::new(2, 2))
+        );
+        // Below it, the lower neighbors do.
+        let coord = BoardCoord::new(3, 1);
+        assert_eq!(
+            coord.neighbor::<3>(HexDirection::LowerLeft),
+            Some(BoardCoord::new(4, 0))
+        );
+        assert_eq!(
+            coord.neighbor::<3>(HexDirection::UpperRight),
+            Some(BoardCoord::new(2, 2))
+        );
+        assert_eq!(
+            BoardCoord::new(0, 0).neighbor::<3>(HexDirection::Left),
+            None
+        );
+        assert_eq!(
+            BoardCoord::new(4, 2).neighbor::<3>(HexDirection::LowerRight),
+            None
+        );
+    }
+
+    #[test]
+    fn test_line_to() {
+        let line: Vec<_> = BoardCoord::new(0, 0)
+            .line_to::<3>(&BoardCoord::new(4, 2))
+            .collect();
+        assert_eq!(
+            line,
+            [
+                BoardCoord::new(0, 0),
+                BoardCoord::new(1, 1),
+                BoardCoord::new(2, 2),
+                BoardCoord::new(3, 2),
+                BoardCoord::new(4, 2),
+            ]
+        );
+
+        let start = BoardCoord::new(0, 1);
+        let end = BoardCoord::new(3, 0);
+        let line: Vec<_> = start.line_to::<3>(&end).collect();
+        assert_eq!(line.len(), start.distance::<3>(&end) + 1);
+        assert!(line.windows(2).all(|w| w[0].is_adjacent::<3>(&w[1])));
+        assert_eq!(start.line_to::<3>(&start).collect::<Vec<_>>(), [start]);
+    }
 }
-pub type MatchSets = HashSet<MatchSet>;
@@ -1,6 +1,9 @@
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+use crate::coord::BoardCoord;
+use crate::tile::{MetalTile, Tile};
+
+#[derive(Debug, Error, PartialEq, Eq)]
 pub enum BoardParseError {
     #[error("Invalid row count: expected {0}, found {1}")]
     InvalidRowCount(usize, usize),
@@ -8,10 +11,136 @@ pub enum BoardParseError {
     InvalidRowLength(usize, usize),
     #[error("Unexpected tile character: {0}")]
     UnexpectedTileCharacter(char),
+    #[error("Tile in row {0}, column {1} is out of line with the hexagon")]
+    MisalignedTile(usize, usize),
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Unknown tile: {0:?}")]
+pub struct TileParseError(pub String);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BoardDecodeError {
+    #[error("Invalid encoded board length: expected {expected} bytes, found {found}")]
+    WrongLength { expected: usize, found: usize },
+    #[error("Unsupported binary board format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Encoded board has size {found}, expected {expected}")]
+    WrongSize { expected: usize, found: usize },
+    #[error("Invalid tile code: {0}")]
+    InvalidTileCode(u8),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Expected {expected} tiles for the board, found {found}")]
+pub struct TileCountError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CoordError {
+    #[error("Coordinate {0:?} is off a board of size {1}")]
+    OffBoard(BoardCoord, usize),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Invalid coordinate {0:?}: expected `row,col` or `rRcC`")]
+pub struct CoordParseError(pub String);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Boards conflict at {coord:?}: {ours:?} and {theirs:?}")]
+pub struct OverlapError {
+    pub coord: BoardCoord,
+    pub ours: Tile,
+    pub theirs: Tile,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
 pub enum MatchSetError {
     #[error("MatchSet cannot be empty")]
     EmptyMatchSet,
+    #[error("MatchSet cannot contain {0} twice")]
+    DuplicateCoord(BoardCoord),
+}
+
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    #[error("Failed to read or write checkpoint: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed checkpoint at line {0}")]
+    MalformedLine(usize),
+    #[error("Checkpoint ended unexpectedly")]
+    UnexpectedEnd,
+    #[error("Invalid board in checkpoint: {0}")]
+    InvalidBoard(#[from] BoardParseError),
+    #[error("Invalid step in checkpoint: {0}")]
+    InvalidStep(String),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SolutionError {
+    #[error("Move {0} is not a legal match")]
+    IllegalMove(usize),
+    #[error("Board is not cleared at the end of the solution")]
+    BoardNotCleared,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SolutionParseError {
+    #[error("Invalid board: {0}")]
+    Board(#[from] BoardParseError),
+    #[error("Expected a blank line after the board, then the moves")]
+    MissingMoves,
+    #[error("Line {line}: {source}")]
+    Coord {
+        line: usize,
+        source: CoordParseError,
+    },
+    #[error("Line {line}: coordinate {coord} is off the board")]
+    OffBoard { line: usize, coord: BoardCoord },
+    #[error("Line {line}: {source}")]
+    Tile { line: usize, source: TileParseError },
+    #[error("Line {line}: expected {expected:?} at {coord}, but the board has {found:?}")]
+    TileMismatch {
+        line: usize,
+        coord: BoardCoord,
+        expected: Tile,
+        found: Tile,
+    },
+    #[error("Line {line}: {source}")]
+    MatchSet { line: usize, source: MatchSetError },
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BoardBuildError {
+    #[error("Coordinate {0:?} is off the board")]
+    OutOfRange(BoardCoord),
+    #[error("Coordinate {0:?} already holds {1:?}")]
+    Occupied(BoardCoord, Tile),
+    #[error("Board already has gold")]
+    DuplicateGold,
+    #[error("Board already has {0:?}")]
+    DuplicateMetal(MetalTile),
+    #[error("Too many {tile:?} tiles: a board holds at most {limit}")]
+    TooMany { tile: Tile, limit: usize },
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvalidMoveError {
+    #[error("Coordinate {0:?} is off the board")]
+    OutOfRange(BoardCoord),
+    #[error("There is no tile at {0:?}")]
+    EmptyTile(BoardCoord),
+    #[error("The tile at {0:?} is not selectable")]
+    NotSelectable(BoardCoord),
+    #[error("Tiles {0:?} do not match each other")]
+    IllegalMatch(Vec<Tile>),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Move {index} in the history is invalid: {source}")]
+pub struct HistoryError {
+    pub index: usize,
+    pub source: InvalidMoveError,
 }
@@ -0,0 +1,257 @@
+use crate::astar::{FewestMoves, MoveCost};
+use crate::board::Board;
+use crate::coord::{BoardCoord, MatchSet};
+use crate::errors::{HistoryError, InvalidMoveError};
+use crate::math::board_area;
+use crate::solution::Move;
+use crate::tile::Tile;
+
+/// Observes every tile a [`GameState`] changes, e.g. to animate removals or mirror the board.
+///
+/// Both methods do nothing by default.
+pub trait BoardListener {
+    /// Called for each tile removed by a move, with the tile that was there.
+    fn on_remove(&mut self, _coord: BoardCoord, _tile: Tile) {}
+    /// Called for each tile put back on the board by an undo.
+    fn on_set(&mut self, _coord: BoardCoord, _tile: Tile) {}
+}
+impl BoardListener for () {}
+impl<L: BoardListener> BoardListener for &mut L {
+    fn on_remove(&mut self, coord: BoardCoord, tile: Tile) {
+        (**self).on_remove(coord, tile);
+    }
+    fn on_set(&mut self, coord: BoardCoord, tile: Tile) {
+        (**self).on_set(coord, tile);
+    }
+}
+
+/// A game in progress: a board plus the moves made on it, with undo and redo.
+///
+/// Changes to the board are reported to the [`BoardListener`] `L`, which by default ignores them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameState<const S: usize, L = ()>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    initial: Board<S>,
+    board: Board<S>,
+    /// Moves made, each with the tiles it removed so it can be undone.
+    history: Vec<Move>,
+    /// Undone moves, with the most recently undone last.
+    undone: Vec<MatchSet>,
+    listener: L,
+}
+
+impl<const S: usize> GameState<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Start a new game on `board`.
+    pub fn new(board: Board<S>) -> Self {
+        Self {
+            initial: board.clone(),
+            board,
+            history: Vec::new(),
+            undone: Vec::new(),
+            listener: (),
+        }
+    }
+
+    /// Start a game on `board` and replay `moves`, failing at the first illegal one.
+    pub fn from_history(
+        board: Board<S>,
+        moves: impl IntoIterator<Item = MatchSet>,
+    ) -> Result<Self, HistoryError> {
+        let mut game = Self::new(board);
+        for (index, match_set) in moves.into_iter().enumerate() {
+            game.play(match_set)
+                .map_err(|source| HistoryError { index, source })?;
+        }
+        Ok(game)
+    }
+}
+
+impl<const S: usize, L: BoardListener> GameState<S, L>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Report changes from now on to `listener` instead.
+    pub fn with_listener<M: BoardListener>(self, listener: M) -> GameState<S, M> {
+        GameState {
+            initial: self.initial,
+            board: self.board,
+            history: self.history,
+            undone: self.undone,
+            listener,
+        }
+    }
+    pub fn listener(&self) -> &L {
+        &self.listener
+    }
+    pub fn listener_mut(&mut self) -> &mut L {
+        &mut self.listener
+    }
+
+    /// The board as it is now.
+    pub fn board(&self) -> &Board<S> {
+        &self.board
+    }
+    /// The board the game started from.
+    pub fn initial_board(&self) -> &Board<S> {
+        &self.initial
+    }
+    /// The moves made so far, oldest first, each costing 1. Undone moves aren't included.
+    pub fn history(&self) -> &[Move] {
+        &self.history
+    }
+    /// The moves made so far, oldest first, with their cost under `cost`.
+    pub fn history_with_cost(&self, cost: &impl MoveCost<Board<S>>) -> Vec<Move> {
+        Move::sequence(
+            &self.initial,
+            self.history
+                .iter()
+                .map(|played| played.match_set.match_set().clone()),
+            cost,
+        )
+    }
+    pub fn is_solved(&self) -> bool {
+        self.board.is_empty()
+    }
+
+    /// Make a move, if it is legal. This clears the moves available to [`GameState::redo`].
+    pub fn play(&mut self, match_set: MatchSet) -> Result<(), InvalidMoveError> {
+        self.apply(match_set)?;
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Take back the last move, returning it, or `None` if no moves have been made.
+    pub fn undo(&mut self) -> Option<MatchSet> {
+        let played = self.history.pop()?;
+        for (coord, tile) in played.match_set.iter() {
+            self.board.set_tile(coord, tile);
+            self.listener.on_set(coord, tile);
+        }
+        let match_set = played.match_set.into_match_set();
+        self.undone.push(match_set.clone());
+        Some(match_set)
+    }
+    /// Make the last undone move again, returning it, or `None` if there is nothing to redo.
+    pub fn redo(&mut self) -> Option<MatchSet> {
+        let match_set = self.undone.pop()?;
+        self.apply(match_set.clone())
+            .expect("undone moves are legal on the board they were undone from");
+        Some(match_set)
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    fn apply(&mut self, match_set: MatchSet) -> Result<(), InvalidMoveError> {
+        match_set.validate(&self.board)?;
+        let played = Move::new(&self.board, self.history.len(), match_set, &FewestMoves);
+        self.board.remove_match_set(played.match_set.match_set());
+        for (coord, tile) in played.match_set.iter() {
+            self.listener.on_remove(coord, tile);
+        }
+        self.history.push(played);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve::MoveType;
+    use crate::tile::ElementTile;
+
+    #[test]
+    fn test_undo_and_redo() {
+        let fire_tile = Tile::Element(ElementTile::Fire);
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), fire_tile),
+            (BoardCoord::new(4, 2), fire_tile),
+            (BoardCoord::new(2, 2), Tile::Gold),
+        ]);
+        let fire = MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(4, 2)]);
+        let gold = MatchSet::from([BoardCoord::new(2, 2)]);
+
+        let mut game = GameState::new(board.clone());
+        game.play(fire.clone()).unwrap();
+        game.play(gold.clone()).unwrap();
+        assert!(game.is_solved());
+
+        assert_eq!(game.undo(), Some(gold.clone()));
+        assert_eq!(game.undo(), Some(fire.clone()));
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.board(), &board);
+
+        assert_eq!(game.redo(), Some(fire.clone()));
+        assert_eq!(game.history().len(), 1);
+        assert_eq!(game.history()[0].match_set.match_set(), &fire);
+        // A new move discards the rest of the redo stack.
+        game.play(gold.clone()).unwrap();
+        assert!(!game.can_redo());
+
+        let moves = game.history();
+        assert_eq!(
+            moves.iter().map(|m| m.index).collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert_eq!(
+            moves[0].match_set.tile_at(&BoardCoord::new(0, 0)),
+            Some(fire_tile)
+        );
+        assert_eq!(moves[1].move_type, MoveType::Gold);
+        assert!(moves.iter().all(|m| m.cost == 1));
+
+        let replayed = GameState::from_history(
+            board.clone(),
+            moves.iter().map(|m| m.match_set.match_set().clone()),
+        )
+        .unwrap();
+        assert_eq!(replayed.board(), game.board());
+        assert_eq!(
+            GameState::from_history(board, [gold.clone(), gold]).err(),
+            Some(HistoryError {
+                index: 1,
+                source: InvalidMoveError::EmptyTile(BoardCoord::new(2, 2))
+            })
+        );
+    }
+
+    #[test]
+    fn test_listener_sees_changes() {
+        #[derive(Default)]
+        struct Recorder(Vec<(&'static str, BoardCoord, Tile)>);
+        impl BoardListener for Recorder {
+            fn on_remove(&mut self, coord: BoardCoord, tile: Tile) {
+                self.0.push(("remove", coord, tile));
+            }
+            fn on_set(&mut self, coord: BoardCoord, tile: Tile) {
+                self.0.push(("set", coord, tile));
+            }
+        }
+
+        let board = Board::<3>::from_iter([(BoardCoord::new(2, 2), Tile::Gold)]);
+        let gold = MatchSet::from([BoardCoord::new(2, 2)]);
+        let mut recorder = Recorder::default();
+        let mut game = GameState::new(board).with_listener(&mut recorder);
+        game.play(gold.clone()).unwrap();
+        game.undo();
+        game.redo();
+        assert!(game.play(gold).is_err());
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                ("remove", BoardCoord::new(2, 2), Tile::Gold),
+                ("set", BoardCoord::new(2, 2), Tile::Gold),
+                ("remove", BoardCoord::new(2, 2), Tile::Gold),
+            ]
+        );
+    }
+}
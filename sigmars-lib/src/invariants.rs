@@ -0,0 +1,360 @@
+use crate::board::Board;
+use crate::math::board_area;
+use crate::tile::{BinaryTile, ElementTile, MetalTile, Tile};
+
+/// How many of each tile a board in the real game starts with.
+pub const STANDARD_TILE_COUNTS: [(Tile, usize); 14] = [
+    (Tile::Element(ElementTile::Air), 8),
+    (Tile::Element(ElementTile::Fire), 8),
+    (Tile::Element(ElementTile::Water), 8),
+    (Tile::Element(ElementTile::Earth), 8),
+    (Tile::Theta, 4),
+    (Tile::Binary(BinaryTile::Life), 4),
+    (Tile::Binary(BinaryTile::Death), 4),
+    (Tile::Quicksilver, 5),
+    (Tile::Metal(MetalTile::Lead), 1),
+    (Tile::Metal(MetalTile::Tin), 1),
+    (Tile::Metal(MetalTile::Iron), 1),
+    (Tile::Metal(MetalTile::Copper), 1),
+    (Tile::Metal(MetalTile::Silver), 1),
+    (Tile::Gold, 1),
+];
+/// Number of tiles on a board in the real game.
+pub const STANDARD_TILE_TOTAL: usize = 55;
+
+/// A way a board differs from the tile distribution of the real game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardViolation {
+    WrongTileCount {
+        tile: Tile,
+        expected: usize,
+        found: usize,
+    },
+    WrongTotal {
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Something structurally wrong with a starting board, found by [`Board::check_structure`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StructureWarning {
+    /// There is no gold to finish the board with.
+    MissingGold,
+    /// A metal or gold appears more than once, so the metals can't be projected in order.
+    DuplicateMetal { tile: Tile, count: usize },
+    /// There is more quicksilver than metals for it to project.
+    ExcessQuicksilver { quicksilver: usize, metals: usize },
+    /// There are more of a tile than the real game ever deals.
+    TooManyOfTile {
+        tile: Tile,
+        limit: usize,
+        found: usize,
+    },
+    /// There are more tiles than the real game ever deals.
+    TooManyTiles { limit: usize, found: usize },
+}
+
+/// A board-wide condition which proves that a board can never be cleared,
+/// regardless of the order in which moves are made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvariantViolation {
+    /// Vitae and mors only ever clear each other, so their counts must match.
+    UnbalancedBinary { life: usize, death: usize },
+    /// Every element with an odd count needs a salt to absorb the leftover tile,
+    /// and the remaining salt must still pair off evenly.
+    UnpairedElements { odd_elements: usize, salt: usize },
+    /// Each quicksilver projects exactly one metal (excluding gold).
+    QuicksilverMismatch { quicksilver: usize, metals: usize },
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// List every invariant this board violates.
+    ///
+    /// These checks only look at tile counts, so they are cheap enough to run on every search node.
+    pub fn invariant_violations(&self) -> Vec<InvariantViolation> {
+        let census = self.census();
+        let (life, death, salt) = (census.life, census.death, census.theta);
+        let quicksilver = census.quicksilver;
+        let metals = census.metals_remaining();
+
+        let mut violations = Vec::new();
+        if life != death {
+            violations.push(InvariantViolation::UnbalancedBinary { life, death });
+        }
+        let odd_elements = ElementTile::ALL
+            .into_iter()
+            .filter(|&element| census.elements_remaining(element) % 2 == 1)
+            .count();
+        // Each quintessence match takes one of every element, flipping all of their parities, so
+        // the odd count moves between k and 4 - k. That keeps its parity but may need less salt.
+        #[cfg(feature = "quintessence")]
+        let salt_needed = if census.quintessence > 0 {
+            odd_elements.min(ElementTile::ALL.len() - odd_elements)
+        } else {
+            odd_elements
+        };
+        #[cfg(not(feature = "quintessence"))]
+        let salt_needed = odd_elements;
+        if salt_needed > salt || (salt + odd_elements) % 2 == 1 {
+            violations.push(InvariantViolation::UnpairedElements { odd_elements, salt });
+        }
+        if quicksilver != metals {
+            violations.push(InvariantViolation::QuicksilverMismatch {
+                quicksilver,
+                metals,
+            });
+        }
+        violations
+    }
+
+    /// Check this board against the tile distribution of the real game, reporting every difference.
+    pub fn validate_standard(&self) -> Result<(), Vec<StandardViolation>> {
+        let census = self.census();
+        let mut violations: Vec<StandardViolation> = STANDARD_TILE_COUNTS
+            .iter()
+            .filter_map(|&(tile, expected)| {
+                let found = census.count(tile);
+                (found != expected).then_some(StandardViolation::WrongTileCount {
+                    tile,
+                    expected,
+                    found,
+                })
+            })
+            .collect();
+        let found = census.total();
+        if found != STANDARD_TILE_TOTAL {
+            violations.push(StandardViolation::WrongTotal {
+                expected: STANDARD_TILE_TOTAL,
+                found,
+            });
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// List every structural problem with this board as a starting board.
+    ///
+    /// Unlike [`Board::validate_standard`], boards with fewer tiles than the real game are fine,
+    /// as long as what is there could make sense.
+    pub fn check_structure(&self) -> Vec<StructureWarning> {
+        let census = self.census();
+        let mut warnings = Vec::new();
+        if census.gold == 0 {
+            warnings.push(StructureWarning::MissingGold);
+        }
+        for (tile, limit) in STANDARD_TILE_COUNTS {
+            let found = census.count(tile);
+            if found <= limit {
+                continue;
+            }
+            warnings.push(match tile {
+                Tile::Metal(_) | Tile::Gold => {
+                    StructureWarning::DuplicateMetal { tile, count: found }
+                }
+                _ => StructureWarning::TooManyOfTile { tile, limit, found },
+            });
+        }
+        let (quicksilver, metals) = (census.quicksilver, census.metals_remaining());
+        if quicksilver > metals {
+            warnings.push(StructureWarning::ExcessQuicksilver {
+                quicksilver,
+                metals,
+            });
+        }
+        let found = census.total();
+        if found > STANDARD_TILE_TOTAL {
+            warnings.push(StructureWarning::TooManyTiles {
+                limit: STANDARD_TILE_TOTAL,
+                found,
+            });
+        }
+        warnings
+    }
+
+    /// Whether any invariant proves this board unsolvable.
+    pub fn is_provably_unsolvable(&self) -> bool {
+        !self.invariant_violations().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coord::BoardCoord;
+
+    #[test]
+    fn test_empty_board_has_no_violations() {
+        assert!(Board::<3>::empty().invariant_violations().is_empty());
+    }
+
+    #[test]
+    fn test_unbalanced_binary() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Binary(BinaryTile::Life)),
+            (BoardCoord::new(0, 2), Tile::Binary(BinaryTile::Life)),
+            (BoardCoord::new(4, 0), Tile::Binary(BinaryTile::Death)),
+        ]);
+
+        assert_eq!(
+            board.invariant_violations(),
+            vec![InvariantViolation::UnbalancedBinary { life: 2, death: 1 }]
+        );
+    }
+
+    #[test]
+    fn test_odd_elements_fixed_by_salt() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 2), Tile::Theta),
+        ]);
+        assert!(!board.is_provably_unsolvable());
+
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 2), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(4, 0), Tile::Theta),
+        ]);
+        assert_eq!(
+            board.invariant_violations(),
+            vec![InvariantViolation::UnpairedElements {
+                odd_elements: 2,
+                salt: 1
+            }]
+        );
+    }
+
+    #[cfg(feature = "quintessence")]
+    #[test]
+    fn test_quintessence_covers_odd_elements() {
+        use crate::ruleset::ClassicRules;
+
+        let mut board = Board::<3>::from_iter([
+            (BoardCoord::new(2, 2), Tile::Quintessence),
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 2), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(2, 0), Tile::Element(ElementTile::Air)),
+            (BoardCoord::new(2, 4), Tile::Element(ElementTile::Earth)),
+            (BoardCoord::new(4, 0), Tile::Element(ElementTile::Earth)),
+            (BoardCoord::new(4, 2), Tile::Theta),
+        ]);
+        assert!(board.invariant_violations().is_empty());
+
+        // Quintessence takes one of each element, then the salt pairs the last earth.
+        let rules = ClassicRules {
+            quintessence: true,
+            ..ClassicRules::default()
+        };
+        let quintessence_set = board
+            .find_match_sets_with(&rules)
+            .into_iter()
+            .find(|set| set.contains(&BoardCoord::new(2, 2)))
+            .unwrap();
+        board.remove_match_set(&quintessence_set);
+        assert!(board.invariant_violations().is_empty());
+        let salt_set = board.find_match_sets().into_iter().next().unwrap();
+        board.remove_match_set(&salt_set);
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn test_quicksilver_mismatch() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Metal(MetalTile::Lead)),
+            (BoardCoord::new(0, 2), Tile::Metal(MetalTile::Tin)),
+            (BoardCoord::new(4, 0), Tile::Quicksilver),
+        ]);
+
+        assert_eq!(
+            board.invariant_violations(),
+            vec![InvariantViolation::QuicksilverMismatch {
+                quicksilver: 1,
+                metals: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_structure() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(2, 2), Tile::Gold),
+            (BoardCoord::new(0, 0), Tile::Metal(MetalTile::Lead)),
+            (BoardCoord::new(0, 2), Tile::Quicksilver),
+        ]);
+        assert_eq!(board.check_structure(), vec![]);
+
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Metal(MetalTile::Lead)),
+            (BoardCoord::new(0, 1), Tile::Metal(MetalTile::Lead)),
+            (BoardCoord::new(1, 0), Tile::Quicksilver),
+            (BoardCoord::new(1, 1), Tile::Quicksilver),
+            (BoardCoord::new(1, 2), Tile::Quicksilver),
+        ]);
+        assert_eq!(
+            board.check_structure(),
+            vec![
+                StructureWarning::MissingGold,
+                StructureWarning::DuplicateMetal {
+                    tile: Tile::Metal(MetalTile::Lead),
+                    count: 2
+                },
+                StructureWarning::ExcessQuicksilver {
+                    quicksilver: 3,
+                    metals: 2
+                },
+            ]
+        );
+
+        let board = Board::<6>::from_tiles([Tile::Theta; board_area::<6>()]);
+        assert_eq!(
+            board.check_structure(),
+            vec![
+                StructureWarning::MissingGold,
+                StructureWarning::TooManyOfTile {
+                    tile: Tile::Theta,
+                    limit: 4,
+                    found: 91
+                },
+                StructureWarning::TooManyTiles {
+                    limit: 55,
+                    found: 91
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_standard() {
+        let mut board = Board::<6>::empty();
+        let mut coords = crate::math::coords::<6>();
+        for (tile, count) in STANDARD_TILE_COUNTS {
+            for coord in coords.by_ref().take(count) {
+                board.set_tile(coord, tile);
+            }
+        }
+        assert_eq!(board.validate_standard(), Ok(()));
+
+        board.set_tile(BoardCoord::new(0, 0), Tile::Gold);
+        assert_eq!(
+            board.validate_standard(),
+            Err(vec![
+                StandardViolation::WrongTileCount {
+                    tile: Tile::Element(ElementTile::Air),
+                    expected: 8,
+                    found: 7
+                },
+                StandardViolation::WrongTileCount {
+                    tile: Tile::Gold,
+                    expected: 1,
+                    found: 2
+                },
+            ])
+        );
+    }
+}
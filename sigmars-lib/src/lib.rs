@@ -1,15 +1,49 @@
 #![feature(generic_const_exprs)]
 #![allow(incomplete_features)]
 
+mod alphabet;
+mod analysis;
+mod astar;
 mod board;
+mod builder;
+mod census;
 mod coord;
 mod errors;
+mod game;
+mod invariants;
 pub mod math;
+mod multiset;
+mod notation;
+mod persistent;
+mod phases;
+mod regions;
+mod render;
+mod ruleset;
+mod search;
+mod solution;
 mod solve;
+mod solver;
+mod symmetry;
 mod tile;
 
+pub use crate::alphabet::*;
+pub use crate::analysis::*;
+pub use crate::astar::*;
 pub use crate::board::*;
+pub use crate::builder::*;
+pub use crate::census::*;
 pub use crate::coord::*;
 pub use crate::errors::*;
+pub use crate::game::*;
+pub use crate::invariants::*;
+pub use crate::multiset::*;
+pub use crate::persistent::*;
+pub use crate::regions::*;
+pub use crate::render::*;
+pub use crate::ruleset::*;
+pub use crate::search::*;
+pub use crate::solution::*;
 pub use crate::solve::*;
+pub use crate::solver::*;
+pub use crate::symmetry::*;
 pub use crate::tile::*;
@@ -1,3 +1,5 @@
+use crate::coord::BoardCoord;
+
 pub const fn board_area<const S: usize>() -> usize {
     1 + 3 * S * (S - 1)
 }
@@ -9,3 +11,49 @@ pub const fn row_length<const S: usize>(row: usize) -> usize {
     assert!(row < row_count::<S>());
     if row < S { S + row } else { 3 * S - 2 - row }
 }
+
+/// Whether `(row, col)` is a position on a board of size `S`.
+pub const fn coord_in_bounds<const S: usize>(row: usize, col: usize) -> bool {
+    row < row_count::<S>() && col < row_length::<S>(row)
+}
+
+/// Every position on a board of size `S`, row by row, in the same order as the board's tiles.
+pub fn coords<const S: usize>() -> impl Iterator<Item = BoardCoord> {
+    (0..row_count::<S>()).flat_map(coords_in_row::<S>)
+}
+/// The positions in `row` of a board of size `S`, left to right.
+pub fn coords_in_row<const S: usize>(row: usize) -> impl Iterator<Item = BoardCoord> {
+    (0..row_length::<S>(row)).map(move |col| BoardCoord::new(row, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coord_in_bounds() {
+        assert!(coord_in_bounds::<3>(0, 2));
+        assert!(!coord_in_bounds::<3>(0, 3));
+        assert!(coord_in_bounds::<3>(2, 4));
+        assert!(!coord_in_bounds::<3>(5, 0));
+    }
+
+    #[test]
+    fn test_coords() {
+        assert_eq!(coords::<3>().count(), board_area::<3>());
+        assert!(coords::<3>().all(|coord| coord.is_on_board::<3>()));
+        assert!(
+            coords::<4>()
+                .enumerate()
+                .all(|(idx, coord)| coord.as_index::<4>() == idx)
+        );
+        assert_eq!(
+            coords_in_row::<3>(4).collect::<Vec<_>>(),
+            vec![
+                BoardCoord::new(4, 0),
+                BoardCoord::new(4, 1),
+                BoardCoord::new(4, 2)
+            ]
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ops::{Add, AddAssign, Sub};
+
+use rand::Rng;
+
+use crate::board::Board;
+use crate::invariants::STANDARD_TILE_COUNTS;
+use crate::math::board_area;
+use crate::tile::Tile;
+
+/// A bag of tiles: how many of each tile, with no positions.
+///
+/// Multisets are compared by inclusion, so `a <= b` when `b` has at least as many of every tile
+/// as `a`, and two multisets with different tiles to spare are not comparable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TileMultiset {
+    /// Only tiles with a non-zero count are kept, so equal multisets have equal maps.
+    counts: BTreeMap<Tile, usize>,
+}
+impl TileMultiset {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The tiles a board in the real game starts with.
+    pub fn standard() -> Self {
+        STANDARD_TILE_COUNTS.into_iter().collect()
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.counts.get(&tile).copied().unwrap_or(0)
+    }
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+    /// Each tile with its count, in sorted order, skipping tiles with none.
+    pub fn iter(&self) -> impl Iterator<Item = (Tile, usize)> + '_ {
+        self.counts.iter().map(|(tile, count)| (*tile, *count))
+    }
+
+    pub fn insert(&mut self, tile: Tile, count: usize) {
+        if count > 0 {
+            *self.counts.entry(tile).or_default() += count;
+        }
+    }
+    /// Take up to `count` of `tile` out, returning how many were taken.
+    pub fn remove(&mut self, tile: Tile, count: usize) -> usize {
+        let Some(have) = self.counts.get_mut(&tile) else {
+            return 0;
+        };
+        let taken = count.min(*have);
+        *have -= taken;
+        if *have == 0 {
+            self.counts.remove(&tile);
+        }
+        taken
+    }
+
+    /// A tile chosen at random, weighted by count, or `None` if the multiset is empty.
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<Tile> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut index = rng.random_range(0..self.total());
+        self.iter().find_map(|(tile, count)| {
+            if index < count {
+                Some(tile)
+            } else {
+                index -= count;
+                None
+            }
+        })
+    }
+
+    /// `self` minus `other`, or `None` if `other` has more of some tile.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        other.is_subset(self).then(|| self - other)
+    }
+    /// Whether `other` has at least as many of every tile.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.iter().all(|(tile, count)| other.count(tile) >= count)
+    }
+}
+
+impl FromIterator<(Tile, usize)> for TileMultiset {
+    fn from_iter<T: IntoIterator<Item = (Tile, usize)>>(iter: T) -> Self {
+        let mut multiset = Self::new();
+        for (tile, count) in iter {
+            multiset.insert(tile, count);
+        }
+        multiset
+    }
+}
+impl FromIterator<Tile> for TileMultiset {
+    fn from_iter<T: IntoIterator<Item = Tile>>(iter: T) -> Self {
+        iter.into_iter().map(|tile| (tile, 1)).collect()
+    }
+}
+
+/// Counts every non-empty tile on the board.
+impl<const S: usize> From<&Board<S>> for TileMultiset
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn from(board: &Board<S>) -> Self {
+        board.nonempty_tiles().map(|(_, tile)| *tile).collect()
+    }
+}
+
+impl AddAssign<&TileMultiset> for TileMultiset {
+    fn add_assign(&mut self, other: &TileMultiset) {
+        for (tile, count) in other.iter() {
+            self.insert(tile, count);
+        }
+    }
+}
+impl Add for &TileMultiset {
+    type Output = TileMultiset;
+
+    fn add(self, other: &TileMultiset) -> TileMultiset {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+/// Removes as many of each tile as `other` has, stopping at zero. See
+/// [`TileMultiset::checked_sub`] to detect when `other` has more.
+impl Sub for &TileMultiset {
+    type Output = TileMultiset;
+
+    fn sub(self, other: &TileMultiset) -> TileMultiset {
+        let mut difference = self.clone();
+        for (tile, count) in other.iter() {
+            difference.remove(tile, count);
+        }
+        difference
+    }
+}
+
+impl PartialOrd for TileMultiset {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.is_subset(other), other.is_subset(self)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Deals tiles at random from a [`TileMultiset`], without replacement.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TileBag {
+    remaining: TileMultiset,
+}
+impl TileBag {
+    pub fn new(tiles: TileMultiset) -> Self {
+        Self { remaining: tiles }
+    }
+    /// A bag of the tiles a board in the real game starts with.
+    pub fn standard() -> Self {
+        Self::new(TileMultiset::standard())
+    }
+
+    /// Take one tile out of the bag, each remaining tile being equally likely.
+    pub fn draw(&mut self, rng: &mut impl Rng) -> Option<Tile> {
+        let tile = self.remaining.pick(rng)?;
+        self.remaining.remove(tile, 1);
+        Some(tile)
+    }
+    /// The tiles still in the bag.
+    pub fn remaining(&self) -> &TileMultiset {
+        &self.remaining
+    }
+    pub fn len(&self) -> usize {
+        self.remaining.total()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::coord::BoardCoord;
+    use crate::tile::ElementTile;
+
+    #[test]
+    fn test_multiset_arithmetic() {
+        let fire = Tile::Element(ElementTile::Fire);
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), fire),
+            (BoardCoord::new(0, 2), fire),
+            (BoardCoord::new(2, 2), Tile::Gold),
+        ]);
+        let on_board = TileMultiset::from(&board);
+        assert_eq!(on_board.count(fire), 2);
+        assert_eq!(on_board.total(), 3);
+
+        let standard = TileMultiset::standard();
+        assert_eq!(standard.total(), 55);
+        assert!(on_board < standard);
+        let missing = standard.checked_sub(&on_board).unwrap();
+        assert_eq!(missing.count(fire), 6);
+        assert_eq!(missing.count(Tile::Gold), 0);
+        assert_eq!(&missing + &on_board, standard);
+        assert_eq!(on_board.checked_sub(&standard), None);
+
+        let salt = TileMultiset::from_iter([Tile::Theta]);
+        assert_eq!(on_board.partial_cmp(&salt), None);
+        assert_eq!(&salt - &on_board, salt);
+    }
+
+    #[test]
+    fn test_bag_deals_without_replacement() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut bag = TileBag::standard();
+        let dealt: TileMultiset = std::iter::from_fn(|| bag.draw(&mut rng)).collect();
+        assert_eq!(dealt, TileMultiset::standard());
+        assert!(bag.is_empty());
+        assert_eq!(bag.draw(&mut rng), None);
+
+        for _ in 0..20 {
+            assert!(TileMultiset::standard().count(Tile::sample_standard(&mut rng)) > 0);
+        }
+    }
+}
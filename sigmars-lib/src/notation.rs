@@ -0,0 +1,133 @@
+use crate::board::Board;
+use crate::errors::{BoardDecodeError, BoardParseError};
+use crate::math::board_area;
+use crate::tile::Tile;
+
+/// Version byte at the start of the binary board format, with two tiles to a byte.
+const BINARY_FORMAT_VERSION: u8 = 1;
+/// Version byte for boards with tiles that don't fit in four bits, with one tile to a byte.
+const WIDE_BINARY_FORMAT_VERSION: u8 = 2;
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Write this board on a single line, e.g. `bLc/bAWc/...`.
+    ///
+    /// Rows are written in the board text format and joined by `/`, except that each run of empty
+    /// tiles is written as one lowercase letter giving its length (`a` = 1, `b` = 2, ... `z` = 26).
+    pub fn to_compact(&self) -> String {
+        self.rows()
+            .map(|row| {
+                let mut out = String::new();
+                let mut empties = 0u8;
+                for tile in row {
+                    match tile {
+                        Tile::Empty => {
+                            empties += 1;
+                            if empties == 26 {
+                                out.push('z');
+                                empties = 0;
+                            }
+                        }
+                        tile => {
+                            if empties > 0 {
+                                out.push((b'a' + empties - 1) as char);
+                                empties = 0;
+                            }
+                            out.push(tile.to_char());
+                        }
+                    }
+                }
+                if empties > 0 {
+                    out.push((b'a' + empties - 1) as char);
+                }
+                out
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Parse a board written by [`Board::to_compact`].
+    pub fn from_compact(s: &str) -> Result<Self, BoardParseError> {
+        let rows: Vec<String> = s
+            .trim()
+            .split('/')
+            .map(|row| {
+                row.chars()
+                    .map(|c| match c {
+                        'a'..='z' => "_".repeat((c as u8 - b'a' + 1) as usize),
+                        c => c.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        rows.join("\n").parse()
+    }
+
+    /// Encode this board in a compact binary format.
+    ///
+    /// The first byte is the format version (currently 1) and the second is the board size `S`.
+    /// Then every tile follows in row-major order as its [`Tile::to_u8`] code, two to a byte with
+    /// the first tile in the high nibble. An odd final nibble is padded with 0.
+    ///
+    /// Boards with blocked cells, whose code doesn't fit in four bits, are written in version 2
+    /// instead, with every tile code in a byte of its own.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let codes: Vec<u8> = self.tiles().map(|tile| tile.to_u8()).collect();
+        if codes.iter().any(|code| *code > 0xf) {
+            let mut bytes = vec![WIDE_BINARY_FORMAT_VERSION, S as u8];
+            bytes.extend(codes);
+            return bytes;
+        }
+        let mut bytes = vec![BINARY_FORMAT_VERSION, S as u8];
+        bytes.extend(
+            codes
+                .chunks(2)
+                .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0)),
+        );
+        bytes
+    }
+
+    /// Decode a board written by [`Board::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BoardDecodeError> {
+        let narrow_len = 2 + board_area::<S>().div_ceil(2);
+        if bytes.len() < 2 {
+            return Err(BoardDecodeError::WrongLength {
+                expected: narrow_len,
+                found: bytes.len(),
+            });
+        }
+        let expected_len = match bytes[0] {
+            BINARY_FORMAT_VERSION => narrow_len,
+            WIDE_BINARY_FORMAT_VERSION => 2 + board_area::<S>(),
+            version => return Err(BoardDecodeError::UnsupportedVersion(version)),
+        };
+        if bytes[1] as usize != S {
+            return Err(BoardDecodeError::WrongSize {
+                expected: S,
+                found: bytes[1] as usize,
+            });
+        }
+        if bytes.len() != expected_len {
+            return Err(BoardDecodeError::WrongLength {
+                expected: expected_len,
+                found: bytes.len(),
+            });
+        }
+
+        let mut tiles = [Tile::Empty; board_area::<S>()];
+        let codes: Vec<u8> = if bytes[0] == WIDE_BINARY_FORMAT_VERSION {
+            bytes[2..].to_vec()
+        } else {
+            bytes[2..]
+                .iter()
+                .flat_map(|byte| [byte >> 4, byte & 0xf])
+                .collect()
+        };
+        for (tile, code) in tiles.iter_mut().zip(codes) {
+            *tile = Tile::from_u8(code).ok_or(BoardDecodeError::InvalidTileCode(code))?;
+        }
+        Ok(Self::from_tiles(tiles))
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::{Arc, OnceLock};
+
+use crate::board::Board;
+use crate::coord::{IntoBoardCoord, MatchSet};
+use crate::math::board_area;
+use crate::solve::Solvable;
+use crate::tile::Tile;
+
+/// A board whose rows are shared between copies, so [`PersistentBoard::without_match_set`] only
+/// copies the rows a move touches.
+///
+/// This saves memory when many related boards are kept alive at once, as in the open set of an
+/// [`astar`](crate::astar()) search. Move generation goes through a [`Board`], built once per
+/// board the first time a search asks about it and not copied by `clone`, so a plain `Board` is
+/// still faster when boards are short-lived.
+#[derive(Debug)]
+pub struct PersistentBoard<const S: usize>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    rows: Vec<Arc<[Tile]>>,
+    board: OnceLock<Board<S>>,
+}
+impl<const S: usize> Clone for PersistentBoard<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            rows: self.rows.clone(),
+            board: OnceLock::new(),
+        }
+    }
+}
+impl<const S: usize> PartialEq for PersistentBoard<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows
+    }
+}
+impl<const S: usize> Eq for PersistentBoard<S> where [(); board_area::<S>()]: Sized {}
+impl<const S: usize> std::hash::Hash for PersistentBoard<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.rows.hash(state);
+    }
+}
+
+impl<const S: usize> PersistentBoard<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    pub fn get_tile(&self, coord: impl IntoBoardCoord) -> &Tile {
+        let coord = coord.into_board_coord::<S>();
+        &self.rows[coord.row][coord.col]
+    }
+    /// Set the tile at `coord`, copying its row first if another board shares it.
+    pub fn set_tile(&mut self, coord: impl IntoBoardCoord, tile: Tile) {
+        let coord = coord.into_board_coord::<S>();
+        assert!(coord.is_on_board::<S>());
+        self.board = OnceLock::new();
+        Arc::make_mut(&mut self.rows[coord.row])[coord.col] = tile;
+    }
+    pub fn remove_match_set(&mut self, match_set: &MatchSet) {
+        for coord in match_set.iter() {
+            self.set_tile(coord, Tile::Empty);
+        }
+    }
+    pub fn without_match_set(&self, match_set: &MatchSet) -> Self {
+        let mut new_board = self.clone();
+        new_board.remove_match_set(match_set);
+        new_board
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles().all(|tile| !tile.needs_clearing())
+    }
+    /// Every tile in row-major order, as with [`Board::tiles`].
+    pub fn tiles(&self) -> impl Iterator<Item = &Tile> {
+        self.rows.iter().flat_map(|row| row.iter())
+    }
+
+    pub fn to_board(&self) -> Board<S> {
+        self.board().clone()
+    }
+    /// The equivalent [`Board`], built on first use and kept until the board changes.
+    fn board(&self) -> &Board<S> {
+        self.board.get_or_init(|| {
+            let tiles: Vec<Tile> = self.tiles().copied().collect();
+            Board::try_from(tiles).expect("persistent boards hold one tile per cell")
+        })
+    }
+}
+
+impl<const S: usize> From<&Board<S>> for PersistentBoard<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn from(board: &Board<S>) -> Self {
+        Self {
+            rows: board.rows().map(Arc::from).collect(),
+            board: OnceLock::new(),
+        }
+    }
+}
+impl<const S: usize> From<&PersistentBoard<S>> for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn from(board: &PersistentBoard<S>) -> Self {
+        board.to_board()
+    }
+}
+
+/// Solved like the equivalent [`Board`], without symmetry reduction.
+impl<const S: usize> Solvable for PersistentBoard<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    type Step = MatchSet;
+
+    fn is_goal(&self) -> bool {
+        self.is_empty()
+    }
+    fn next_steps(&self) -> Vec<MatchSet> {
+        self.board().next_steps()
+    }
+    fn apply_step(&self, step: &MatchSet) -> Self {
+        self.without_match_set(step)
+    }
+    fn is_dead_end(&self) -> bool {
+        self.board().is_dead_end()
+    }
+    fn forced_step(&self) -> Option<MatchSet> {
+        self.board().forced_step()
+    }
+    fn step_priority(&self, step: &MatchSet) -> Option<u32> {
+        self.board().step_priority(step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coord::BoardCoord;
+    use crate::solve::solve_dfs;
+    use crate::tile::ElementTile;
+
+    #[test]
+    fn test_moves_share_untouched_rows() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 2), Tile::Gold),
+        ]);
+        let persistent = PersistentBoard::from(&board);
+        assert_eq!(persistent.to_board(), board);
+
+        let after = persistent.without_match_set(&MatchSet::from([BoardCoord::new(2, 2)]));
+        assert_eq!(after.get_tile(BoardCoord::new(2, 2)), &Tile::Empty);
+        assert_eq!(persistent.get_tile(BoardCoord::new(2, 2)), &Tile::Gold);
+        for row in [0, 1, 3, 4] {
+            assert!(Arc::ptr_eq(&persistent.rows[row], &after.rows[row]));
+        }
+        assert!(!Arc::ptr_eq(&persistent.rows[2], &after.rows[2]));
+
+        // Every search hook on one board shares a single `Board`, which copies don't inherit.
+        assert!(after.board.get().is_none());
+        let steps = after.next_steps();
+        let built: *const Board<3> = after.board.get().unwrap();
+        assert!(!after.is_dead_end());
+        assert_eq!(after.forced_step(), Some(steps[0].clone()));
+        assert!(after.step_priority(&steps[0]).is_some());
+        assert!(std::ptr::eq(after.board.get().unwrap(), built));
+        assert!(after.clone().board.get().is_none());
+
+        let solution = solve_dfs(&persistent).into_solution().unwrap();
+        assert_eq!(solution, solve_dfs(&board).into_solution().unwrap());
+    }
+}
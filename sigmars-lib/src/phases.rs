@@ -0,0 +1,107 @@
+use crate::board::Board;
+use crate::coord::MatchSet;
+use crate::math::board_area;
+use crate::solve::{Solvable, SolveOutcome};
+use crate::solver::Solver;
+
+/// One phase of a phased solve: clear the lowest remaining metal, or the whole board once the
+/// metals are gone. Later metals can't be projected yet, so they act as obstacles.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MetalPhase<const S: usize>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    board: Board<S>,
+    /// Number of metals on the board when the phase started.
+    metals: usize,
+}
+
+impl<const S: usize> Solvable for MetalPhase<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    type Step = MatchSet;
+
+    fn is_goal(&self) -> bool {
+        if self.metals == 0 {
+            self.board.is_empty()
+        } else {
+            metal_count(&self.board) < self.metals
+        }
+    }
+    fn next_steps(&self) -> Vec<MatchSet> {
+        self.board.next_steps()
+    }
+    fn apply_step(&self, step: &MatchSet) -> Self {
+        Self {
+            board: self.board.apply_step(step),
+            metals: self.metals,
+        }
+    }
+    fn is_dead_end(&self) -> bool {
+        self.board.is_dead_end()
+    }
+    fn forced_step(&self) -> Option<MatchSet> {
+        self.board.forced_step()
+    }
+    fn step_priority(&self, step: &MatchSet) -> Option<u32> {
+        self.board.step_priority(step)
+    }
+    fn canonicalize(&self) -> Self {
+        Self {
+            board: self.board.canonicalize(),
+            metals: self.metals,
+        }
+    }
+}
+
+fn metal_count<const S: usize>(board: &Board<S>) -> usize
+where
+    [(); board_area::<S>()]: Sized,
+{
+    board.census().metals_remaining()
+}
+
+impl Solver {
+    /// Solve `board` one metal at a time: first search for a way to project the lowest metal,
+    /// then the next, and so on, finishing with a search for the rest of the board.
+    ///
+    /// Each phase is much shallower than the whole solve, but a phase's solution may leave the
+    /// later phases unsolvable. In that case this falls back to a full search of `board`.
+    /// The timeout applies to each search separately.
+    pub fn solve_phased<const S: usize>(&self, board: &Board<S>) -> SolveOutcome<Board<S>>
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        let mut current = board.clone();
+        let mut steps = Vec::new();
+        while !current.is_empty() {
+            let phase = MetalPhase {
+                metals: metal_count(&current),
+                board: current.clone(),
+            };
+            match self.solve(&phase) {
+                SolveOutcome::Solved(phase_steps) => {
+                    for step in &phase_steps {
+                        current.remove_match_set(step);
+                    }
+                    steps.extend(phase_steps);
+                }
+                // Failing the very first phase proves the whole board unsolvable.
+                SolveOutcome::Unsolvable {
+                    states_explored,
+                    dead_ends_pruned,
+                } if steps.is_empty() => {
+                    return SolveOutcome::Unsolvable {
+                        states_explored,
+                        dead_ends_pruned,
+                    };
+                }
+                SolveOutcome::Unsolvable { .. } => return self.solve(board),
+                SolveOutcome::TimedOut => return SolveOutcome::TimedOut,
+                SolveOutcome::Cancelled => return SolveOutcome::Cancelled,
+            }
+        }
+        SolveOutcome::Solved(steps)
+    }
+}
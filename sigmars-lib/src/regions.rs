@@ -0,0 +1,69 @@
+use crate::board::Board;
+use crate::coord::BoardCoord;
+use crate::math::board_area;
+use crate::tile::Tile;
+
+/// The tiles on the edge of a board of size `S`.
+pub fn outer_ring<const S: usize>() -> impl Fn(&BoardCoord) -> bool {
+    |coord| coord.distance_from_center::<S>() == S - 1
+}
+
+/// The tiles within `k` steps of the center of a board of size `S`.
+pub fn center_hex<const S: usize>(k: usize) -> impl Fn(&BoardCoord) -> bool {
+    move |coord| coord.distance_from_center::<S>() <= k
+}
+
+/// The rows above the middle row of a board of size `S`.
+pub fn upper_half<const S: usize>() -> impl Fn(&BoardCoord) -> bool {
+    |coord| coord.row < S - 1
+}
+
+/// The rows below the middle row of a board of size `S`.
+pub fn lower_half<const S: usize>() -> impl Fn(&BoardCoord) -> bool {
+    |coord| coord.row > S - 1
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Every tile whose coordinate is in `region`, including empty tiles, in row-major order.
+    pub fn tiles_in(
+        &self,
+        region: impl Fn(&BoardCoord) -> bool,
+    ) -> impl Iterator<Item = (BoardCoord, &Tile)> {
+        self.tiles()
+            .enumerate()
+            .map(|(idx, tile)| (BoardCoord::from_index::<S>(idx), tile))
+            .filter(move |(coord, _)| region(coord))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prebuilt_regions() {
+        let board = Board::<6>::empty();
+        assert_eq!(board.tiles_in(outer_ring::<6>()).count(), 30);
+        assert_eq!(board.tiles_in(center_hex::<6>(1)).count(), 7);
+        assert_eq!(
+            board.tiles_in(center_hex::<6>(5)).count(),
+            board_area::<6>()
+        );
+
+        let upper = board.tiles_in(upper_half::<6>()).count();
+        let lower = board.tiles_in(lower_half::<6>()).count();
+        assert_eq!(upper, lower);
+        assert_eq!(upper + lower + 11, board_area::<6>());
+    }
+
+    #[test]
+    fn test_tiles_in_custom_region() {
+        let board = Board::<3>::from_iter([(BoardCoord::new(2, 2), Tile::Gold)]);
+        let tiles: Vec<_> = board.tiles_in(|coord| coord.row == 2).collect();
+        assert_eq!(tiles.len(), 5);
+        assert_eq!(tiles[2], (BoardCoord::new(2, 2), &Tile::Gold));
+    }
+}
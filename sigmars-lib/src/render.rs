@@ -0,0 +1,150 @@
+use std::collections::BTreeSet;
+
+use crate::board::Board;
+use crate::coord::MatchSet;
+use crate::math::{board_area, row_count};
+use crate::tile::Tile;
+
+/// Names tiles for display, e.g. to translate them.
+pub trait TileNames {
+    fn tile_name(&self, tile: Tile) -> String;
+}
+/// The names from [`Tile::name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EnglishNames;
+impl TileNames for EnglishNames {
+    fn tile_name(&self, tile: Tile) -> String {
+        tile.name().to_string()
+    }
+}
+impl<F: Fn(Tile) -> String> TileNames for F {
+    fn tile_name(&self, tile: Tile) -> String {
+        self(tile)
+    }
+}
+
+/// How [`Board::render`] draws each tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RenderStyle {
+    /// Text format characters, with empty tiles as `.`.
+    #[default]
+    Ascii,
+    /// The symbols from [`Tile::glyph`].
+    Unicode,
+}
+impl RenderStyle {
+    pub fn tile_char(self, tile: Tile) -> char {
+        match (self, tile) {
+            (RenderStyle::Ascii, Tile::Empty) => '.',
+            (RenderStyle::Ascii, tile) => tile.to_char(),
+            (RenderStyle::Unicode, tile) => tile.glyph(),
+        }
+    }
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Draw the board as a hexagon, followed by a legend of the tiles on it.
+    ///
+    /// Tiles in `highlight` are drawn in brackets, e.g. `[F]`.
+    pub fn render(&self, style: RenderStyle, highlight: Option<&MatchSet>) -> String {
+        self.render_with_names(style, highlight, &EnglishNames)
+    }
+    /// [`Board::render`], with the legend naming tiles with `names`.
+    pub fn render_with_names(
+        &self,
+        style: RenderStyle,
+        highlight: Option<&MatchSet>,
+        names: &impl TileNames,
+    ) -> String {
+        let mut out = String::new();
+        for row in self.rows_enumerated() {
+            let mut line = String::new();
+            let mut row_len = 0;
+            for (coord, tile) in row {
+                let glyph = style.tile_char(*tile);
+                if highlight.is_some_and(|set| set.contains(&coord)) {
+                    line.push_str(&format!(" [{glyph}]"));
+                } else {
+                    line.push_str(&format!("  {glyph} "));
+                }
+                row_len += 1;
+            }
+            // Each tile is four characters wide, so shorter rows shift by half a tile per tile.
+            out.push_str(&" ".repeat(2 * (row_count::<S>() - row_len)));
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+
+        let present: BTreeSet<Tile> = self.nonempty_tiles().map(|(_, tile)| *tile).collect();
+        if !present.is_empty() {
+            out.push('\n');
+        }
+        for tile in present {
+            out.push_str(&format!(
+                "{} {}\n",
+                style.tile_char(tile),
+                names.tile_name(tile)
+            ));
+        }
+        out
+    }
+    /// [`Board::render`] in [`RenderStyle::Ascii`].
+    pub fn render_ascii(&self, highlight: Option<&MatchSet>) -> String {
+        self.render(RenderStyle::Ascii, highlight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coord::BoardCoord;
+    use crate::tile::ElementTile;
+
+    #[test]
+    fn test_render_ascii() {
+        let board = Board::<2>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(1, 1), Tile::Gold),
+            (BoardCoord::new(2, 1), Tile::Element(ElementTile::Fire)),
+        ]);
+        let highlight = MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(2, 1)]);
+        assert_eq!(
+            board.render_ascii(Some(&highlight)),
+            concat!(
+                "   [F]  .\n",
+                "  .   5   .\n",
+                "    .  [F]\n",
+                "\n",
+                "F Fire\n",
+                "5 Gold\n",
+            )
+        );
+        assert_eq!(
+            Board::<2>::empty().render_ascii(None),
+            "    .   .\n  .   .   .\n    .   .\n"
+        );
+        assert_eq!(
+            board.render(RenderStyle::Unicode, None),
+            concat!(
+                "    🜂   ·\n",
+                "  ·   ☉   ·\n",
+                "    ·   🜂\n",
+                "\n",
+                "🜂 Fire\n",
+                "☉ Gold\n",
+            )
+        );
+        let translated = |tile: Tile| match tile {
+            Tile::Gold => "Gold".to_string(),
+            tile => format!("{} (translated)", tile.name()),
+        };
+        assert!(
+            board
+                .render_with_names(RenderStyle::Ascii, None, &translated)
+                .ends_with("F Fire (translated)\n5 Gold\n")
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use crate::board::Board;
+use crate::census::{MetalProgression, TileCensus};
+use crate::coord::{BoardCoord, MatchSet, MatchSets};
+use crate::math::board_area;
+use crate::tile::Tile;
+
+/// The rules deciding which tiles can be cleared together, and when a board is won.
+///
+/// Rules are given the [`TileCensus`] of the board, so they can depend on what is left on it
+/// (e.g. which metal is next). Only selectable tiles are ever offered to a ruleset.
+pub trait Ruleset {
+    /// Whether `a` and `b` can be cleared together as a pair.
+    fn pairs_with(&self, a: Tile, b: Tile, census: &TileCensus) -> bool;
+    /// Whether `tile` can be cleared on its own.
+    fn clears_alone(&self, tile: Tile, census: &TileCensus) -> bool;
+    /// How many tiles of the same element clear together. Above two, same-element tiles are
+    /// matched in groups of this size instead of with [`Ruleset::pairs_with`].
+    fn element_match_size(&self) -> usize {
+        2
+    }
+    /// Whether a quintessence can be cleared with one of each element.
+    #[cfg(feature = "quintessence")]
+    fn allows_quintessence(&self) -> bool {
+        false
+    }
+    /// How eager a solver should be to clear `tile`. Defaults to [`Tile::base_priority`].
+    fn base_priority(&self, tile: Tile) -> Option<u32> {
+        tile.base_priority()
+    }
+    /// Whether `board` counts as won. Defaults to the board being cleared.
+    fn is_won<const S: usize>(&self, board: &Board<S>) -> bool
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        board.is_empty()
+    }
+}
+
+/// The rules of the original game, with a few switches for house rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClassicRules {
+    /// Whether gold can only be cleared once every other metal is gone.
+    pub gold_after_metals: bool,
+    /// Whether two salts can clear each other, rather than only elements.
+    pub salt_pairs_with_salt: bool,
+    /// How many tiles of the same element clear together, e.g. 3 for triples.
+    pub element_match_size: usize,
+    /// Whether a board with only [`Tile::Blocked`] cells left counts as won. If not, no board
+    /// with blocked cells can be won.
+    pub ignore_blocked: bool,
+    /// Whether a quintessence can be cleared with one of each element.
+    #[cfg(feature = "quintessence")]
+    pub quintessence: bool,
+}
+impl Default for ClassicRules {
+    fn default() -> Self {
+        Self {
+            gold_after_metals: false,
+            salt_pairs_with_salt: true,
+            element_match_size: 2,
+            ignore_blocked: true,
+            #[cfg(feature = "quintessence")]
+            quintessence: false,
+        }
+    }
+}
+impl Ruleset for ClassicRules {
+    fn pairs_with(&self, a: Tile, b: Tile, census: &TileCensus) -> bool {
+        match (a, b) {
+            (Tile::Element(a), Tile::Element(b)) => a == b && self.element_match_size == 2,
+            (Tile::Theta, Tile::Theta) => self.salt_pairs_with_salt,
+            (Tile::Element(_), Tile::Theta) | (Tile::Theta, Tile::Element(_)) => true,
+            (Tile::Binary(a), Tile::Binary(b)) => a != b,
+            // Quicksilver can only project the lowest metal left on the board.
+            (Tile::Quicksilver, Tile::Metal(metal)) | (Tile::Metal(metal), Tile::Quicksilver) => {
+                MetalProgression::from_census(census).projectable() == Some(metal)
+            }
+            _ => false,
+        }
+    }
+    fn clears_alone(&self, tile: Tile, census: &TileCensus) -> bool {
+        tile == Tile::Gold
+            && (!self.gold_after_metals || MetalProgression::from_census(census).is_gold_unlocked())
+    }
+    #[cfg(feature = "quintessence")]
+    fn allows_quintessence(&self) -> bool {
+        self.quintessence
+    }
+    fn element_match_size(&self) -> usize {
+        self.element_match_size
+    }
+    fn is_won<const S: usize>(&self, board: &Board<S>) -> bool
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        board.is_empty() && (self.ignore_blocked || board.census().blocked == 0)
+    }
+}
+
+/// Every match `rules` allows for the tile at `coord`, among the selectable `candidates`.
+pub(crate) fn matches_for(
+    rules: &impl Ruleset,
+    coord: BoardCoord,
+    tile: Tile,
+    census: &TileCensus,
+    candidates: &[(BoardCoord, &Tile)],
+) -> MatchSets {
+    let mut sets = MatchSets::default();
+    if rules.clears_alone(tile, census) {
+        sets.insert(MatchSet::single(coord));
+    }
+    for (other, other_tile) in candidates {
+        if *other != coord && rules.pairs_with(tile, **other_tile, census) {
+            sets.insert(MatchSet::from([coord, *other]));
+        }
+    }
+    if let Tile::Element(element) = tile
+        && rules.element_match_size() > 2
+    {
+        let others: Vec<BoardCoord> = candidates
+            .iter()
+            .filter(|(c, t)| *c != coord && **t == Tile::Element(element))
+            .map(|(c, _)| *c)
+            .collect();
+        for mut group in combinations(&others, rules.element_match_size() - 1) {
+            group.push(coord);
+            sets.insert(MatchSet::try_from_iter(group).expect("groups include `coord`"));
+        }
+    }
+    #[cfg(feature = "quintessence")]
+    if tile == Tile::Quintessence && rules.allows_quintessence() {
+        sets.extend(quintessence_matches(coord, candidates));
+    }
+    sets
+}
+
+/// Every way to choose `k` of `items`, keeping their order.
+fn combinations(items: &[BoardCoord], k: usize) -> Vec<Vec<BoardCoord>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    (0..items.len())
+        .flat_map(|first| {
+            combinations(&items[first + 1..], k - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, items[first]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+/// Every way to clear the quintessence at `coord` with one of each element.
+#[cfg(feature = "quintessence")]
+fn quintessence_matches(coord: BoardCoord, candidates: &[(BoardCoord, &Tile)]) -> MatchSets {
+    use crate::tile::ElementTile;
+
+    let mut partial = vec![vec![coord]];
+    for element in ElementTile::ALL {
+        let coords: Vec<BoardCoord> = candidates
+            .iter()
+            .filter(|(_, t)| **t == Tile::Element(element))
+            .map(|(c, _)| *c)
+            .collect();
+        partial = partial
+            .into_iter()
+            .flat_map(|set| {
+                coords.iter().map(move |c| {
+                    let mut set = set.clone();
+                    set.push(*c);
+                    set
+                })
+            })
+            .collect();
+    }
+    partial
+        .into_iter()
+        .map(|set| MatchSet::try_from_iter(set).expect("sets start with the quintessence"))
+        .collect()
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Find every legal move under `rules`.
+    pub fn find_match_sets_with(&self, rules: &impl Ruleset) -> MatchSets {
+        // Sorted so a tile with several possible partners always pairs the same way.
+        let mut candidates: Vec<_> = self.selectable_tiles().into_iter().collect();
+        candidates.sort();
+        let census = self.census();
+
+        candidates
+            .iter()
+            .flat_map(|(c, t)| matches_for(rules, *c, **t, &census, &candidates))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::{ElementTile, MetalTile};
+
+    #[test]
+    fn test_house_rules_change_matches() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Theta),
+            (BoardCoord::new(0, 2), Tile::Theta),
+            (BoardCoord::new(2, 2), Tile::Gold),
+            (BoardCoord::new(4, 0), Tile::Metal(MetalTile::Lead)),
+            (BoardCoord::new(4, 2), Tile::Quicksilver),
+        ]);
+        let salt = MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(0, 2)]);
+        let gold = MatchSet::from([BoardCoord::new(2, 2)]);
+
+        let classic = board.find_match_sets_with(&ClassicRules::default());
+        assert!(classic.contains(&salt));
+        assert!(classic.contains(&gold));
+        assert_eq!(classic.len(), 3);
+
+        // The update fills in fields only present with some features.
+        #[allow(clippy::needless_update)]
+        let house = board.find_match_sets_with(&ClassicRules {
+            gold_after_metals: true,
+            salt_pairs_with_salt: false,
+            ..ClassicRules::default()
+        });
+        assert!(!house.contains(&salt));
+        assert!(!house.contains(&gold));
+        assert_eq!(house.len(), 1);
+    }
+
+    #[test]
+    fn test_blocked_tiles() {
+        let mut board = Board::<3>::from_iter([
+            (BoardCoord::new(2, 1), Tile::Blocked),
+            (BoardCoord::new(2, 2), Tile::Gold),
+            (BoardCoord::new(1, 1), Tile::Blocked),
+        ]);
+        assert_eq!(board.free_count(), 1);
+        assert_eq!(board.remaining(), 1);
+        assert_eq!(
+            board.find_match_sets(),
+            MatchSets::from([MatchSet::from([BoardCoord::new(2, 2)])])
+        );
+        assert!(!board.is_provably_unsolvable());
+
+        board.remove_tile(BoardCoord::new(2, 2));
+        assert!(board.is_empty());
+        assert!(board.find_match_sets().is_empty());
+        assert!(ClassicRules::default().is_won(&board));
+        // The update fills in fields only present with some features.
+        #[allow(clippy::needless_update)]
+        let strict = ClassicRules {
+            ignore_blocked: false,
+            ..ClassicRules::default()
+        };
+        assert!(!strict.is_won(&board));
+    }
+
+    #[test]
+    fn test_element_triples() {
+        let fire = Tile::Element(ElementTile::Fire);
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), fire),
+            (BoardCoord::new(0, 2), fire),
+            (BoardCoord::new(2, 0), fire),
+            (BoardCoord::new(2, 4), fire),
+            (BoardCoord::new(4, 0), Tile::Theta),
+        ]);
+        // The update fills in fields only present with some features.
+        #[allow(clippy::needless_update)]
+        let triples = ClassicRules {
+            element_match_size: 3,
+            ..ClassicRules::default()
+        };
+        let sets = board.find_match_sets_with(&triples);
+        let fire_sets: Vec<&MatchSet> = sets.iter().filter(|set| set.len() == 3).collect();
+        // Four fires can be grouped in threes four ways.
+        assert_eq!(fire_sets.len(), 4);
+        assert!(sets.contains(&MatchSet::from([
+            BoardCoord::new(0, 0),
+            BoardCoord::new(0, 2),
+            BoardCoord::new(2, 4)
+        ])));
+        // Salt still pairs with any one element.
+        assert_eq!(sets.iter().filter(|set| set.len() == 2).count(), 4);
+        assert_eq!(board.find_match_sets().len(), 6 + 4);
+    }
+
+    #[test]
+    fn test_quicksilver_takes_lowest_metal() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Metal(MetalTile::Tin)),
+            (BoardCoord::new(0, 2), Tile::Metal(MetalTile::Lead)),
+            (BoardCoord::new(4, 2), Tile::Quicksilver),
+            (BoardCoord::new(4, 0), Tile::Element(ElementTile::Fire)),
+        ]);
+        assert_eq!(
+            board.find_match_sets_with(&ClassicRules::default()),
+            MatchSets::from([MatchSet::from([
+                BoardCoord::new(0, 2),
+                BoardCoord::new(4, 2)
+            ])])
+        );
+    }
+}
@@ -0,0 +1,651 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+use std::time::Instant;
+
+use crate::board::Board;
+use crate::coord::{BoardCoord, MatchSet};
+use crate::errors::CheckpointError;
+use crate::math::{board_area, row_count, row_length};
+use crate::solve::{CancelToken, ProgressEvent, Solvable};
+use crate::solver::{EvictionPolicy, SolverOptions};
+
+/// How many states are visited between progress callbacks.
+const PROGRESS_INTERVAL: usize = 1024;
+
+/// First line of every checkpoint, identifying the format version.
+const CHECKPOINT_HEADER: &str = "sigmars-search 2";
+
+/// A [`Solvable`] whose states and steps can be written to a search checkpoint.
+///
+/// Encoded states and steps must fit on a single line, and encoded steps must not contain spaces.
+pub trait Checkpoint: Solvable {
+    /// Encode a state as a single line of text.
+    fn encode_state(&self) -> String;
+    /// Decode a state written by `encode_state`.
+    fn decode_state(s: &str) -> Result<Self, CheckpointError>;
+    /// Encode a step as a single word of text.
+    fn encode_step(step: &Self::Step) -> String;
+    /// Decode a step written by `encode_step`.
+    fn decode_step(s: &str) -> Result<Self::Step, CheckpointError>;
+}
+
+/// The result of advancing a [`SearchState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchProgress<G: Solvable> {
+    /// A solution was found.
+    Solved(Vec<G::Step>),
+    /// Every reachable state was explored without finding a solution.
+    Exhausted,
+    /// The search stopped early, and can be continued by advancing it again.
+    Paused,
+}
+impl<G: Solvable> SearchProgress<G> {
+    /// The solution, if one was found.
+    pub fn into_solution(self) -> Option<Vec<G::Step>> {
+        match self {
+            SearchProgress::Solved(solution) => Some(solution),
+            SearchProgress::Exhausted | SearchProgress::Paused => None,
+        }
+    }
+}
+
+/// Why a search chose not to expand a state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rejection {
+    /// The state is already as deep as the move bound allows.
+    MoveLimit,
+    /// The state (or a symmetric variant of it) was already visited.
+    Seen,
+    /// [`Solvable::is_dead_end`] proved the state unsolvable.
+    DeadEnd,
+    /// A caller-supplied heuristic pruned the state.
+    Pruned,
+}
+
+/// A step a search is about to try, along with its [`Solvable::step_priority`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate<G: Solvable> {
+    pub step: G::Step,
+    pub priority: Option<u32>,
+}
+
+/// One decision made by a traced search. See [`Solver::solve_traced`](crate::Solver::solve_traced).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceEvent<G: Solvable> {
+    /// A state at `depth` was expanded, and its candidates will be tried first to last.
+    /// Only the forced step is listed when the state has one.
+    Expanded {
+        depth: usize,
+        candidates: Vec<Candidate<G>>,
+    },
+    /// Taking `step` led to a state at `depth` which was not expanded.
+    Rejected {
+        depth: usize,
+        step: G::Step,
+        reason: Rejection,
+    },
+    /// Every candidate of the state at `depth` failed, so the search backed out of it.
+    Backtracked { depth: usize },
+}
+
+/// When a state in the seen set was visited.
+#[derive(Debug, Clone, Copy)]
+struct Seen {
+    /// The shallowest depth the state was visited at.
+    depth: usize,
+    /// Value of the search's visit clock when the state was last reached, unique per visit.
+    last_visit: usize,
+}
+
+/// One state on the path currently being explored.
+#[derive(Clone)]
+struct Frame<G: Solvable> {
+    state: G,
+    /// Steps not yet tried from this state, with the highest priority step last.
+    pending: Vec<G::Step>,
+    /// The step taken from the previous frame to reach this state, or `None` for the root.
+    step: Option<G::Step>,
+}
+
+/// A hook which reorders the steps of a state in place before they are tried, first to last.
+pub(crate) type StepOrder<'a, G> = dyn FnMut(&G, &mut Vec<<G as Solvable>::Step>) + 'a;
+
+/// Limits and hooks for a single call to [`SearchState::run`].
+pub(crate) struct RunOptions<'a, G: Solvable> {
+    /// Pause after visiting this many more states.
+    pub max_states: Option<usize>,
+    /// Pause once this instant has passed.
+    pub deadline: Option<Instant>,
+    /// Pause once this token is cancelled.
+    pub cancel: Option<&'a CancelToken>,
+    /// Pause after backing out of this many states whose steps all failed.
+    pub max_backtracks: Option<usize>,
+    pub on_progress: Option<&'a mut dyn FnMut(ProgressEvent)>,
+    pub order: Option<&'a mut StepOrder<'a, G>>,
+    /// States for which this returns `true` are not expanded.
+    pub prune: Option<&'a dyn Fn(&G) -> bool>,
+    /// Record every decision the search makes here.
+    pub trace: Option<&'a mut Vec<TraceEvent<G>>>,
+}
+impl<G: Solvable> Default for RunOptions<'_, G> {
+    fn default() -> Self {
+        Self {
+            max_states: None,
+            deadline: None,
+            cancel: None,
+            max_backtracks: None,
+            on_progress: None,
+            order: None,
+            prune: None,
+            trace: None,
+        }
+    }
+}
+
+/// The complete state of a depth-first search, which can be paused, checkpointed, and resumed.
+///
+/// The search is driven by an explicit stack of frames rather than recursion,
+/// so its depth is only bounded by available memory.
+#[derive(Clone)]
+pub struct SearchState<G: Solvable> {
+    root: G,
+    frames: Vec<Frame<G>>,
+    /// Canonical states visited so far, unless evicted to stay under `seen_limit`.
+    seen: HashMap<G, Seen>,
+    /// Ticks once per visit, so no two seen states share a `last_visit`.
+    clock: usize,
+    states_explored: usize,
+    max_depth: usize,
+    /// Number of visited states pruned as dead ends.
+    dead_ends: usize,
+    max_moves: Option<usize>,
+    prune_dead_ends: bool,
+    use_symmetry: bool,
+    seen_limit: Option<usize>,
+    eviction: EvictionPolicy,
+}
+
+impl<G: Solvable> SearchState<G> {
+    /// Start a new search from `root`, using the move bound and pruning settings from `options`.
+    pub fn new(root: &G, options: &SolverOptions) -> Self {
+        Self {
+            root: root.clone(),
+            frames: Vec::new(),
+            seen: HashMap::new(),
+            clock: 0,
+            states_explored: 0,
+            max_depth: 0,
+            dead_ends: 0,
+            max_moves: options.max_moves,
+            prune_dead_ends: options.prune_dead_ends,
+            use_symmetry: options.use_symmetry,
+            seen_limit: options.seen_limit,
+            eviction: options.eviction,
+        }
+    }
+
+    /// Number of distinct states visited so far.
+    ///
+    /// States evicted from the seen set and later visited again are counted again.
+    pub fn states_explored(&self) -> usize {
+        self.states_explored
+    }
+    /// Number of visited states pruned because [`Solvable::is_dead_end`] proved them unsolvable.
+    pub fn dead_ends(&self) -> usize {
+        self.dead_ends
+    }
+
+    /// Continue the search until it finishes or visits `max_states` more states.
+    pub fn advance(&mut self, max_states: usize) -> SearchProgress<G> {
+        self.run(RunOptions {
+            max_states: Some(max_states),
+            ..Default::default()
+        })
+    }
+
+    /// Continue the search until it finishes or hits one of the limits in `options`.
+    pub(crate) fn run(&mut self, mut options: RunOptions<G>) -> SearchProgress<G> {
+        let started = Instant::now();
+        let starting_states = self.states_explored;
+        let mut report = |search: &Self| {
+            if let Some(on_progress) = options.on_progress.as_mut() {
+                let elapsed = started.elapsed();
+                let states_this_run = search.states_explored - starting_states;
+                on_progress(ProgressEvent {
+                    states_explored: search.states_explored,
+                    states_per_sec: states_this_run as f64
+                        / elapsed.as_secs_f64().max(f64::EPSILON),
+                    depth: search.frames.len().saturating_sub(1),
+                    max_depth: search.max_depth,
+                    elapsed,
+                });
+            }
+        };
+
+        if self.root.is_goal() {
+            return SearchProgress::Solved(Vec::new());
+        }
+        // The root is only visited on the first run.
+        if self.states_explored == 0 && self.visit(&self.root.clone(), 0, options.prune).is_ok() {
+            self.frames.push(Frame {
+                state: self.root.clone(),
+                pending: Self::pending_steps(&self.root, 0, &mut options.order, &mut options.trace),
+                step: None,
+            });
+        }
+
+        let mut backtracks = 0;
+        let mut reported_states = self.states_explored;
+        let result = loop {
+            if self.frames.is_empty() {
+                break SearchProgress::Exhausted;
+            }
+            // Checked every iteration, so a long run of rejected states still stops on time.
+            if self.states_explored - reported_states >= PROGRESS_INTERVAL {
+                report(self);
+                reported_states = self.states_explored;
+            }
+            if options
+                .max_states
+                .is_some_and(|max_states| self.states_explored - starting_states >= max_states)
+                || options
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+                || options.cancel.is_some_and(CancelToken::is_cancelled)
+            {
+                break SearchProgress::Paused;
+            }
+
+            let frame = self.frames.last_mut().expect("checked above");
+            let Some(step) = frame.pending.pop() else {
+                self.frames.pop();
+                if let Some(trace) = options.trace.as_mut() {
+                    trace.push(TraceEvent::Backtracked {
+                        depth: self.frames.len(),
+                    });
+                }
+                backtracks += 1;
+                if !self.frames.is_empty()
+                    && options
+                        .max_backtracks
+                        .is_some_and(|max_backtracks| backtracks >= max_backtracks)
+                {
+                    break SearchProgress::Paused;
+                }
+                continue;
+            };
+
+            let next = frame.state.apply_step(&step);
+            let depth = self.frames.len();
+            self.max_depth = self.max_depth.max(depth);
+            if next.is_goal() {
+                let mut solution = self.path();
+                solution.push(step);
+                break SearchProgress::Solved(solution);
+            }
+            if let Err(reason) = self.visit(&next, depth, options.prune) {
+                if let Some(trace) = options.trace.as_mut() {
+                    trace.push(TraceEvent::Rejected {
+                        depth,
+                        step,
+                        reason,
+                    });
+                }
+                continue;
+            }
+            self.frames.push(Frame {
+                pending: Self::pending_steps(&next, depth, &mut options.order, &mut options.trace),
+                state: next,
+                step: Some(step),
+            });
+        };
+        report(self);
+        result
+    }
+
+    /// The steps taken to reach the state currently being explored.
+    fn path(&self) -> Vec<G::Step> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.step.clone())
+            .collect()
+    }
+
+    fn pending_steps(
+        state: &G,
+        depth: usize,
+        order: &mut Option<&mut StepOrder<'_, G>>,
+        trace: &mut Option<&mut Vec<TraceEvent<G>>>,
+    ) -> Vec<G::Step> {
+        // A forced step can't lose a solvable state, so there's no need to try the others.
+        let mut steps = match state.forced_step() {
+            Some(step) => vec![step],
+            None => {
+                let mut steps = state.next_steps();
+                if let Some(order) = order {
+                    order(state, &mut steps);
+                }
+                steps
+            }
+        };
+        if let Some(trace) = trace {
+            trace.push(TraceEvent::Expanded {
+                depth,
+                candidates: steps
+                    .iter()
+                    .map(|step| Candidate {
+                        step: step.clone(),
+                        priority: state.step_priority(step),
+                    })
+                    .collect(),
+            });
+        }
+        steps.reverse();
+        steps
+    }
+
+    /// Shrink the seen set to three quarters of its limit, so evictions happen in batches.
+    ///
+    /// States on the current path are never evicted, so the search can't loop back into them.
+    fn evict(&mut self) {
+        let Some(limit) = self.seen_limit else {
+            return;
+        };
+        let on_path: HashSet<G> = self
+            .frames
+            .iter()
+            .map(|frame| {
+                if self.use_symmetry {
+                    frame.state.canonicalize()
+                } else {
+                    frame.state.clone()
+                }
+            })
+            .collect();
+
+        let mut candidates: Vec<(&G, Seen)> = self
+            .seen
+            .iter()
+            .filter(|(state, _)| !on_path.contains(*state))
+            .map(|(state, seen)| (state, *seen))
+            .collect();
+        // Most expendable first. Visit times are unique, so the order doesn't depend on the
+        // order the seen set happens to iterate in.
+        match self.eviction {
+            EvictionPolicy::KeepShallowest => {
+                candidates.sort_by_key(|(_, seen)| (Reverse(seen.depth), seen.last_visit))
+            }
+            EvictionPolicy::LeastRecentlyUsed => {
+                candidates.sort_by_key(|(_, seen)| seen.last_visit)
+            }
+        }
+        let excess = self.seen.len().saturating_sub(limit * 3 / 4);
+        let evicted: Vec<G> = candidates
+            .into_iter()
+            .take(excess)
+            .map(|(state, _)| state.clone())
+            .collect();
+        for state in evicted {
+            self.seen.remove(&state);
+        }
+    }
+
+    /// Record a visit to `state` at `depth`, returning why it shouldn't be expanded, if it shouldn't.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    fn visit(
+        &mut self,
+        state: &G,
+        depth: usize,
+        prune: Option<&dyn Fn(&G) -> bool>,
+    ) -> Result<(), Rejection> {
+        if self.max_moves.is_some_and(|max_moves| depth >= max_moves) {
+            return Err(Rejection::MoveLimit);
+        }
+        // Prune if we've seen this state (or a symmetric variant of it) before.
+        // With a move bound, a state reached by a shorter path may still succeed, so only prune
+        // revisits that are no shallower than before.
+        let key = if self.use_symmetry {
+            state.canonicalize()
+        } else {
+            state.clone()
+        };
+        self.clock += 1;
+        if let Some(seen) = self.seen.get_mut(&key) {
+            seen.last_visit = self.clock;
+            if self.max_moves.is_none() || seen.depth <= depth {
+                return Err(Rejection::Seen);
+            }
+        }
+        self.seen.insert(
+            key,
+            Seen {
+                depth,
+                last_visit: self.clock,
+            },
+        );
+        self.states_explored += 1;
+        if self.seen_limit.is_some_and(|limit| self.seen.len() > limit) {
+            self.evict();
+        }
+        // Prune if the state can never be solved.
+        if self.prune_dead_ends && state.is_dead_end() {
+            self.dead_ends += 1;
+            return Err(Rejection::DeadEnd);
+        }
+        if prune.is_some_and(|prune| prune(state)) {
+            return Err(Rejection::Pruned);
+        }
+        Ok(())
+    }
+}
+
+impl<G: Checkpoint> SearchState<G> {
+    /// Write this search to `writer` in a line-oriented text format.
+    pub fn save(&self, mut writer: impl Write) -> Result<(), CheckpointError> {
+        writeln!(writer, "{}", CHECKPOINT_HEADER)?;
+        match self.max_moves {
+            Some(max_moves) => writeln!(writer, "max_moves {}", max_moves)?,
+            None => writeln!(writer, "max_moves -")?,
+        }
+        writeln!(writer, "prune_dead_ends {}", self.prune_dead_ends)?;
+        writeln!(writer, "use_symmetry {}", self.use_symmetry)?;
+        match self.seen_limit {
+            Some(limit) => writeln!(writer, "seen_limit {} {}", limit, self.eviction)?,
+            None => writeln!(writer, "seen_limit -")?,
+        }
+        writeln!(writer, "states_explored {}", self.states_explored)?;
+        writeln!(writer, "max_depth {}", self.max_depth)?;
+        writeln!(writer, "dead_ends {}", self.dead_ends)?;
+        writeln!(writer, "root {}", self.root.encode_state())?;
+        for frame in &self.frames {
+            let step = frame
+                .step
+                .as_ref()
+                .map(G::encode_step)
+                .unwrap_or_else(|| "-".to_string());
+            write!(writer, "frame {}", step)?;
+            for pending in &frame.pending {
+                write!(writer, " {}", G::encode_step(pending))?;
+            }
+            writeln!(writer)?;
+        }
+        // Sorted so that saving the same search always writes the same bytes.
+        let mut seen: Vec<String> = self
+            .seen
+            .iter()
+            .map(|(state, seen)| {
+                format!(
+                    "seen {} {} {}",
+                    seen.depth,
+                    seen.last_visit,
+                    state.encode_state()
+                )
+            })
+            .collect();
+        seen.sort();
+        for line in seen {
+            writeln!(writer, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Read a search previously written with [`SearchState::save`], ready to be advanced again.
+    pub fn resume(reader: impl BufRead) -> Result<Self, CheckpointError> {
+        let mut lines = reader.lines().enumerate();
+        let mut next_line = |key: &str| -> Result<(usize, String), CheckpointError> {
+            let (idx, line) = lines.next().ok_or(CheckpointError::UnexpectedEnd)?;
+            let line = line?;
+            line.strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix(' '))
+                .map(|rest| (idx + 1, rest.to_string()))
+                .ok_or(CheckpointError::MalformedLine(idx + 1))
+        };
+
+        let (idx, header) = next_line("sigmars-search")?;
+        if format!("sigmars-search {}", header) != CHECKPOINT_HEADER {
+            return Err(CheckpointError::MalformedLine(idx));
+        }
+        let (idx, max_moves) = next_line("max_moves")?;
+        let max_moves = match max_moves.as_str() {
+            "-" => None,
+            n => Some(n.parse().map_err(|_| CheckpointError::MalformedLine(idx))?),
+        };
+        let (idx, prune_dead_ends) = next_line("prune_dead_ends")?;
+        let prune_dead_ends = prune_dead_ends
+            .parse()
+            .map_err(|_| CheckpointError::MalformedLine(idx))?;
+        let (idx, use_symmetry) = next_line("use_symmetry")?;
+        let use_symmetry = use_symmetry
+            .parse()
+            .map_err(|_| CheckpointError::MalformedLine(idx))?;
+        let (idx, seen_limit) = next_line("seen_limit")?;
+        let (seen_limit, eviction) = match seen_limit.split_once(' ') {
+            None if seen_limit == "-" => (None, EvictionPolicy::default()),
+            Some((limit, eviction)) => (
+                Some(
+                    limit
+                        .parse()
+                        .map_err(|_| CheckpointError::MalformedLine(idx))?,
+                ),
+                eviction
+                    .parse()
+                    .map_err(|_| CheckpointError::MalformedLine(idx))?,
+            ),
+            None => return Err(CheckpointError::MalformedLine(idx)),
+        };
+        let (idx, states_explored) = next_line("states_explored")?;
+        let states_explored = states_explored
+            .parse()
+            .map_err(|_| CheckpointError::MalformedLine(idx))?;
+        let (idx, max_depth) = next_line("max_depth")?;
+        let max_depth = max_depth
+            .parse()
+            .map_err(|_| CheckpointError::MalformedLine(idx))?;
+        let (idx, dead_ends) = next_line("dead_ends")?;
+        let dead_ends = dead_ends
+            .parse()
+            .map_err(|_| CheckpointError::MalformedLine(idx))?;
+        let (_, root) = next_line("root")?;
+        let root = G::decode_state(&root)?;
+
+        let mut search = Self {
+            root,
+            frames: Vec::new(),
+            seen: HashMap::new(),
+            clock: 0,
+            states_explored,
+            max_depth,
+            dead_ends,
+            max_moves,
+            prune_dead_ends,
+            use_symmetry,
+            seen_limit,
+            eviction,
+        };
+        for (idx, line) in lines {
+            let line = line?;
+            let idx = idx + 1;
+            if let Some(frame) = line.strip_prefix("frame ") {
+                let mut steps = frame.split(' ');
+                let step = match steps.next() {
+                    Some("-") if search.frames.is_empty() => None,
+                    Some(step) if !search.frames.is_empty() => Some(G::decode_step(step)?),
+                    _ => return Err(CheckpointError::MalformedLine(idx)),
+                };
+                let pending = steps.map(G::decode_step).collect::<Result<Vec<_>, _>>()?;
+                let state = match (&step, search.frames.last()) {
+                    (Some(step), Some(parent)) => parent.state.apply_step(step),
+                    _ => search.root.clone(),
+                };
+                search.frames.push(Frame {
+                    state,
+                    pending,
+                    step,
+                });
+            } else if let Some(seen) = line.strip_prefix("seen ") {
+                let mut fields = seen.splitn(3, ' ');
+                let (Some(depth), Some(last_visit), Some(state)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    return Err(CheckpointError::MalformedLine(idx));
+                };
+                let seen = Seen {
+                    depth: depth
+                        .parse()
+                        .map_err(|_| CheckpointError::MalformedLine(idx))?,
+                    last_visit: last_visit
+                        .parse()
+                        .map_err(|_| CheckpointError::MalformedLine(idx))?,
+                };
+                search.clock = search.clock.max(seen.last_visit);
+                search.seen.insert(G::decode_state(state)?, seen);
+            } else {
+                return Err(CheckpointError::MalformedLine(idx));
+            }
+        }
+        Ok(search)
+    }
+}
+
+/// Boards are encoded as their rows joined by `/`, and match sets as `row,col` pairs joined by `;`.
+impl<const S: usize> Checkpoint for Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn encode_state(&self) -> String {
+        (0..row_count::<S>())
+            .map(|row| {
+                (0..row_length::<S>(row))
+                    .map(|col| self.get_tile(BoardCoord::new(row, col)).to_char())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+    fn decode_state(s: &str) -> Result<Self, CheckpointError> {
+        Ok(Board::from_str(&s.replace('/', "\n"))?)
+    }
+
+    fn encode_step(step: &MatchSet) -> String {
+        step.iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+    fn decode_step(s: &str) -> Result<MatchSet, CheckpointError> {
+        let invalid = || CheckpointError::InvalidStep(s.to_string());
+        let coords = s
+            .split(';')
+            .map(|coord| {
+                let coord: BoardCoord = coord.parse().map_err(|_| invalid())?;
+                if !Board::<S>::contains(&coord) {
+                    return Err(invalid());
+                }
+                Ok(coord)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        MatchSet::try_from_iter(coords).map_err(|_| invalid())
+    }
+}
@@ -0,0 +1,528 @@
+use crate::alphabet::TileAlphabet;
+use crate::astar::{FewestMoves, MoveCost};
+use crate::board::Board;
+use crate::coord::{BoardCoord, ClockCoord, MatchSet};
+use crate::errors::{MatchSetError, SolutionError, SolutionParseError};
+use crate::math::{board_area, row_count};
+use crate::solve::MoveType;
+use crate::tile::Tile;
+
+/// A [`MatchSet`] with the tile at each of its coordinates when the move was made, so the move
+/// can still be shown or explained after the board changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnnotatedMatchSet {
+    match_set: MatchSet,
+    /// The tile at each coordinate of `match_set`, in coordinate order.
+    tiles: Vec<Tile>,
+}
+impl AnnotatedMatchSet {
+    /// Record the tiles `match_set` covers on `board`.
+    pub fn new<const S: usize>(board: &Board<S>, match_set: MatchSet) -> Self
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        let tiles = match_set
+            .iter()
+            .map(|coord| *board.get_tile(coord))
+            .collect();
+        Self { match_set, tiles }
+    }
+
+    pub fn match_set(&self) -> &MatchSet {
+        &self.match_set
+    }
+    pub fn into_match_set(self) -> MatchSet {
+        self.match_set
+    }
+    /// The tile that was at `coord`, or `None` if `coord` isn't part of the move.
+    pub fn tile_at(&self, coord: &BoardCoord) -> Option<Tile> {
+        self.iter()
+            .find(|(other, _)| other == coord)
+            .map(|(_, tile)| tile)
+    }
+    /// Each coordinate with its tile, in coordinate order.
+    pub fn iter(&self) -> impl Iterator<Item = (BoardCoord, Tile)> + '_ {
+        self.match_set
+            .iter()
+            .copied()
+            .zip(self.tiles.iter().copied())
+    }
+}
+/// Written like a [`MatchSet`], with each tile's character before its coordinate, e.g.
+/// `{F(0,2)+F(2,7)}`.
+impl std::fmt::Display for AnnotatedMatchSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coords: Vec<String> = self
+            .iter()
+            .map(|(coord, tile)| format!("{tile}({coord})"))
+            .collect();
+        write!(f, "{{{}}}", coords.join("+"))
+    }
+}
+
+/// A move in a [`Solution`] or a [`GameState`](crate::GameState) history, with its place in the
+/// sequence, the tiles it removed, the kind of match it makes, and what it cost.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Move {
+    /// Position in the sequence, starting from 0.
+    pub index: usize,
+    pub match_set: AnnotatedMatchSet,
+    pub move_type: MoveType,
+    /// The cost of the move under the [`MoveCost`] it was measured with, 1 per move by default.
+    pub cost: u32,
+}
+impl Move {
+    /// Describe `match_set`, played as move `index` on `board`, with its cost under `cost`.
+    pub fn new<const S: usize>(
+        board: &Board<S>,
+        index: usize,
+        match_set: MatchSet,
+        cost: &impl MoveCost<Board<S>>,
+    ) -> Self
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        Self {
+            index,
+            move_type: MoveType::identify(board, &match_set),
+            cost: cost.cost(board, &match_set),
+            match_set: AnnotatedMatchSet::new(board, match_set),
+        }
+    }
+
+    /// Describe each of `match_sets`, played in order from `board`, with its cost under `cost`.
+    pub fn sequence<const S: usize>(
+        board: &Board<S>,
+        match_sets: impl IntoIterator<Item = MatchSet>,
+        cost: &impl MoveCost<Board<S>>,
+    ) -> Vec<Move>
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        let mut current = board.clone();
+        match_sets
+            .into_iter()
+            .enumerate()
+            .map(|(index, match_set)| {
+                let step = Move::new(&current, index, match_set, cost);
+                current.remove_match_set(step.match_set.match_set());
+                step
+            })
+            .collect()
+    }
+}
+
+/// An ordered sequence of moves which clears a board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution<const S: usize>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    board: Board<S>,
+    moves: Vec<Move>,
+}
+
+impl<const S: usize> Solution<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Annotate a sequence of match sets with the tiles they remove from `board`.
+    pub fn from_steps(board: &Board<S>, match_sets: impl IntoIterator<Item = MatchSet>) -> Self {
+        Self {
+            board: board.clone(),
+            moves: Move::sequence(board, match_sets, &FewestMoves),
+        }
+    }
+
+    /// The board this solution starts from.
+    pub fn board(&self) -> &Board<S> {
+        &self.board
+    }
+    /// Every move in order, each costing 1.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, Move> {
+        self.moves.iter()
+    }
+    /// Total number of moves in this solution.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+    /// Every move in order, with its cost under `cost`.
+    pub fn moves_with_cost(&self, cost: &impl MoveCost<Board<S>>) -> Vec<Move> {
+        Move::sequence(&self.board, self.match_sets(), cost)
+    }
+    fn match_sets(&self) -> impl Iterator<Item = MatchSet> + '_ {
+        self.moves
+            .iter()
+            .map(|step| step.match_set.match_set().clone())
+    }
+
+    /// One numbered move per line, with each tile's character and its position in
+    /// [`ClockCoord`] notation, e.g. `1. 5@0:0`.
+    pub fn to_clock_notation(&self) -> String {
+        let mut out = String::new();
+        for (idx, step) in self.moves.iter().enumerate() {
+            let tiles = step
+                .match_set
+                .iter()
+                .map(|(coord, tile)| format!("{tile}@{}", ClockCoord::from_board_coord::<S>(coord)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            out.push_str(&format!("{}. {tiles}\n", idx + 1));
+        }
+        out
+    }
+
+    /// Iterate over the board after each move, ending with the cleared board.
+    pub fn iter_boards(&self) -> impl Iterator<Item = Board<S>> + '_ {
+        self.moves.iter().scan(self.board.clone(), |board, step| {
+            board.remove_match_set(step.match_set.match_set());
+            Some(board.clone())
+        })
+    }
+
+    /// Check that playing this solution on its board makes only legal moves and clears the board.
+    pub fn verify(&self) -> Result<(), SolutionError> {
+        let mut current = self.board.clone();
+        for (idx, step) in self.moves.iter().enumerate() {
+            let match_set = step.match_set.match_set();
+            if !current.find_match_sets().contains(match_set) {
+                return Err(SolutionError::IllegalMove(idx));
+            }
+            current.remove_match_set(match_set);
+        }
+        if current.is_empty() {
+            Ok(())
+        } else {
+            Err(SolutionError::BoardNotCleared)
+        }
+    }
+
+    /// Rewrite this solution to make fewer element-theta matches, without searching again.
+    ///
+    /// Two element-theta matches of the same element are re-paired into an element match and a
+    /// theta-theta match wherever the solution stays legal. Solutions that don't solve their board
+    /// are returned unchanged.
+    pub fn optimize(&self) -> Self {
+        let mut best = self.clone();
+        if best.verify().is_err() {
+            return best;
+        }
+        while let Some(improved) = best.repair_element_thetas() {
+            best = improved;
+        }
+        best
+    }
+
+    /// Find one legal re-pairing of two element-theta matches, if there is one.
+    fn repair_element_thetas(&self) -> Option<Self> {
+        let split = |step: &Move| {
+            let (elements, thetas): (Vec<(BoardCoord, Tile)>, Vec<_>) = step
+                .match_set
+                .iter()
+                .partition(|(_, tile)| tile.is_element());
+            (elements[0], thetas[0].0)
+        };
+        let element_thetas: Vec<usize> = (0..self.moves.len())
+            .filter(|&idx| self.moves[idx].move_type == MoveType::ElementTheta)
+            .collect();
+
+        for (n, &i) in element_thetas.iter().enumerate() {
+            for &j in &element_thetas[n + 1..] {
+                let ((element_i, tile_i), theta_i) = split(&self.moves[i]);
+                let ((element_j, tile_j), theta_j) = split(&self.moves[j]);
+                if tile_i != tile_j {
+                    continue;
+                }
+                let elements = MatchSet::from([element_i, element_j]);
+                let thetas = MatchSet::from([theta_i, theta_j]);
+                for (first, second) in [(&elements, &thetas), (&thetas, &elements)] {
+                    let mut match_sets: Vec<MatchSet> = self.match_sets().collect();
+                    match_sets[i] = first.clone();
+                    match_sets[j] = second.clone();
+                    let candidate = Self::from_steps(&self.board, match_sets);
+                    if candidate.verify().is_ok() {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct MoveRepr {
+    index: usize,
+    match_set: MatchSet,
+    tiles: Vec<crate::board::TileEntry>,
+    move_type: MoveType,
+    cost: u32,
+}
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct SolutionRepr<'a, const S: usize>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    board: &'a Board<S>,
+    moves: &'a [Move],
+}
+
+/// Serialized as an object with the `index`, the `match_set`, the removed `tiles`, the
+/// `move_type` and the `cost`:
+///
+/// ```json
+/// {
+///   "index": 0,
+///   "match_set": [{ "row": 0, "col": 2 }, { "row": 3, "col": 0 }],
+///   "tiles": [{ "row": 0, "col": 2, "tile": "salt" }, ...],
+///   "move_type": "ElementTheta",
+///   "cost": 1
+/// }
+/// ```
+#[cfg(feature = "serde")]
+impl serde::Serialize for Move {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        MoveRepr {
+            index: self.index,
+            match_set: self.match_set.match_set().clone(),
+            tiles: self
+                .match_set
+                .iter()
+                .map(|(coord, tile)| crate::board::TileEntry {
+                    row: coord.row,
+                    col: coord.col,
+                    tile,
+                })
+                .collect(),
+            move_type: self.move_type,
+            cost: self.cost,
+        }
+        .serialize(serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Move {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let repr = MoveRepr::deserialize(deserializer)?;
+        let mut tiles: Vec<(BoardCoord, Tile)> = repr
+            .tiles
+            .into_iter()
+            .map(|entry| (BoardCoord::new(entry.row, entry.col), entry.tile))
+            .collect();
+        tiles.sort_by_key(|(coord, _)| *coord);
+        if !tiles
+            .iter()
+            .map(|(coord, _)| coord)
+            .eq(repr.match_set.iter())
+        {
+            return Err(D::Error::custom(
+                "tiles must give one tile for each coordinate of the match set",
+            ));
+        }
+        Ok(Self {
+            index: repr.index,
+            match_set: AnnotatedMatchSet {
+                match_set: repr.match_set,
+                tiles: tiles.into_iter().map(|(_, tile)| tile).collect(),
+            },
+            move_type: repr.move_type,
+            cost: repr.cost,
+        })
+    }
+}
+
+/// The solution file format: the starting board in the text format, a blank line, then one move
+/// per line. Each move lists its coordinates in ascending order, each with the tile it removes:
+///
+/// ```text
+/// __F
+/// ___5
+/// _____
+/// ____
+/// F__
+///
+/// 1,3:5
+/// 0,2:F 4,0:F
+/// ```
+impl<const S: usize> std::fmt::Display for Solution<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.board.to_string_with(&TileAlphabet::default()))?;
+        writeln!(f)?;
+        for step in &self.moves {
+            let tiles = step
+                .match_set
+                .iter()
+                .map(|(coord, tile)| format!("{coord}:{tile}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(f, "{tiles}")?;
+        }
+        Ok(())
+    }
+}
+/// Parses the format written by [`Display`](std::fmt::Display). The `:tile` after each coordinate
+/// is optional, and may be a tile character or name; when present it must match the board.
+/// Blank lines between moves are ignored.
+///
+/// This doesn't check the moves are legal; see [`Solution::verify`].
+impl<const S: usize> std::str::FromStr for Solution<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    type Err = SolutionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().collect();
+        // Too few lines for the board is a board error, reported by the board parser.
+        let (board_lines, move_lines) = lines.split_at(row_count::<S>().min(lines.len()));
+        let board = board_lines.join("\n").parse::<Board<S>>()?;
+        if move_lines
+            .first()
+            .is_some_and(|line| !line.trim().is_empty())
+        {
+            return Err(SolutionParseError::MissingMoves);
+        }
+
+        let mut current = board.clone();
+        let mut match_sets = Vec::new();
+        for (offset, text) in move_lines.iter().enumerate() {
+            let line = row_count::<S>() + offset + 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+            let mut coords = Vec::new();
+            for entry in text.split_whitespace() {
+                let (coord, tile) = match entry.split_once(':') {
+                    Some((coord, tile)) => (coord, Some(tile)),
+                    None => (entry, None),
+                };
+                let coord = coord
+                    .parse::<BoardCoord>()
+                    .map_err(|source| SolutionParseError::Coord { line, source })?;
+                if !Board::<S>::contains(&coord) {
+                    return Err(SolutionParseError::OffBoard { line, coord });
+                }
+                if let Some(tile) = tile {
+                    let expected = tile
+                        .parse::<Tile>()
+                        .map_err(|source| SolutionParseError::Tile { line, source })?;
+                    let found = *current.get_tile(coord);
+                    if expected != found {
+                        return Err(SolutionParseError::TileMismatch {
+                            line,
+                            coord,
+                            expected,
+                            found,
+                        });
+                    }
+                }
+                if coords.contains(&coord) {
+                    return Err(SolutionParseError::MatchSet {
+                        line,
+                        source: MatchSetError::DuplicateCoord(coord),
+                    });
+                }
+                coords.push(coord);
+            }
+            let match_set = MatchSet::try_from_iter(coords)
+                .map_err(|source| SolutionParseError::MatchSet { line, source })?;
+            current.remove_match_set(&match_set);
+            match_sets.push(match_set);
+        }
+        Ok(Self::from_steps(&board, match_sets))
+    }
+}
+
+/// Serialized as an object with the starting `board` and the `moves` in order:
+///
+/// ```json
+/// {
+///   "board": { "size": 6, "tiles": [...] },
+///   "moves": [{
+///     "index": 0,
+///     "match_set": [{ "row": 0, "col": 2 }, { "row": 3, "col": 0 }],
+///     "tiles": [{ "row": 0, "col": 2, "tile": "salt" }, ...],
+///     "move_type": "ElementTheta",
+///     "cost": 1
+///   }, ...]
+/// }
+/// ```
+///
+/// Only `board` and each move's `match_set` are read back; the rest is recomputed.
+#[cfg(feature = "serde")]
+impl<const S: usize> serde::Serialize for Solution<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        SolutionRepr {
+            board: &self.board,
+            moves: &self.moves,
+        }
+        .serialize(serializer)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de, const S: usize> serde::Deserialize<'de> for Solution<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct MoveIn {
+            match_set: MatchSet,
+        }
+        #[derive(serde::Deserialize)]
+        #[serde(bound = "")]
+        struct SolutionIn<const S: usize>
+        where
+            [(); board_area::<S>()]: Sized,
+        {
+            board: Board<S>,
+            moves: Vec<MoveIn>,
+        }
+
+        let repr = SolutionIn::<S>::deserialize(deserializer)?;
+        Ok(Self::from_steps(
+            &repr.board,
+            repr.moves.into_iter().map(|m| m.match_set),
+        ))
+    }
+}
+
+impl<'a, const S: usize> IntoIterator for &'a Solution<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Search for a solution to this board.
+    pub fn solve(&self) -> Option<Solution<S>> {
+        crate::solve::solve_dfs(self)
+            .into_solution()
+            .map(|steps| Solution::from_steps(self, steps))
+    }
+}
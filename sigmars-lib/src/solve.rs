@@ -1,9 +1,16 @@
-use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
+use crate::astar::MoveCost;
 use crate::board::Board;
+use crate::census::TileCensus;
 use crate::coord::MatchSet;
 use crate::math::board_area;
-use crate::tile::Tile;
+use crate::ruleset::{ClassicRules, Ruleset};
+use crate::search::{SearchProgress, SearchState};
+use crate::solver::Solver;
+use crate::tile::{BinaryTile, ElementTile, Tile};
 
 /// A trait for types that can be solved using a sequence of steps.
 ///
@@ -13,6 +20,10 @@ use crate::tile::Tile;
 /// - How to generate all possible next steps from the current state (`next_steps`).
 /// - How to apply a step to the current state to produce a new state (`apply_step`).
 ///
+/// Types may also override `is_dead_end` to let solvers prune states that can never reach a goal,
+/// `forced_step` to let solvers skip branching on safe steps,
+/// and `canonicalize` to let solvers treat symmetric states as the same state.
+///
 /// This trait is intended for use in generic puzzle solvers and search algorithms.
 ///
 /// # Requirements
@@ -29,9 +40,32 @@ pub trait Solvable: Clone + Eq + std::hash::Hash {
     fn next_steps(&self) -> Vec<Self::Step>;
     /// Apply a step to the current state, returning the resulting state.
     fn apply_step(&self, step: &Self::Step) -> Self;
+    /// Whether the current state provably cannot reach a goal state.
+    /// This should be cheap, since it is checked for every state visited. Defaults to never pruning.
+    fn is_dead_end(&self) -> bool {
+        false
+    }
+    /// A step which is always safe to take from this state, if one is known.
+    /// Solvers take it without trying any alternatives. Defaults to never forcing a step.
+    fn forced_step(&self) -> Option<Self::Step> {
+        None
+    }
+    /// How strongly `step` is preferred from this state (lower is preferred), if the type ranks steps.
+    /// This is only reported in search traces. Defaults to no priority.
+    fn step_priority(&self, _step: &Self::Step) -> Option<u32> {
+        None
+    }
+    /// Return a representative of every state equivalent to this one (e.g. under rotation).
+    /// Solvers use this to deduplicate visited states. Defaults to the state itself.
+    fn canonicalize(&self) -> Self {
+        self.clone()
+    }
 }
 
-enum MoveType {
+/// The kind of match a move makes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveType {
     Element,
     ElementTheta,
     ThetaTheta,
@@ -41,7 +75,8 @@ enum MoveType {
     Unknown,
 }
 impl MoveType {
-    fn identify<const S: usize>(board: &Board<S>, match_set: &MatchSet) -> MoveType
+    /// Classify the match `match_set` would make on `board`.
+    pub fn identify<const S: usize>(board: &Board<S>, match_set: &MatchSet) -> MoveType
     where
         [(); board_area::<S>()]: Sized,
     {
@@ -50,20 +85,17 @@ impl MoveType {
             .map(|coord| board.get_tile(coord))
             .collect();
 
-        if tiles.iter().all(|t| matches!(t, Tile::Element(_))) {
+        if tiles.iter().all(|t| t.is_element()) {
             MoveType::Element
         } else if tiles.iter().all(|t| matches!(t, Tile::Theta)) {
             MoveType::ThetaTheta
-        } else if tiles
-            .iter()
-            .all(|t| matches!(t, Tile::Element(_) | Tile::Theta))
-        {
+        } else if tiles.iter().all(|t| t.is_element() || **t == Tile::Theta) {
             MoveType::ElementTheta
-        } else if tiles.iter().all(|t| matches!(t, Tile::Binary(_))) {
+        } else if tiles.iter().all(|t| t.is_binary()) {
             MoveType::Duality
         } else if tiles
             .iter()
-            .all(|t| matches!(t, Tile::Metal(_) | Tile::Quicksilver))
+            .all(|t| t.is_metal() || **t == Tile::Quicksilver)
         {
             MoveType::Metal
         } else if tiles.iter().all(|t| **t == Tile::Gold) {
@@ -74,6 +106,18 @@ impl MoveType {
     }
 }
 
+/// Counts element-theta matches, which risk leaving an element without a partner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FewestElementThetas;
+impl<const S: usize> MoveCost<Board<S>> for FewestElementThetas
+where
+    [(); board_area::<S>()]: Sized,
+{
+    fn cost(&self, board: &Board<S>, step: &MatchSet) -> u32 {
+        (MoveType::identify(board, step) == MoveType::ElementTheta) as u32
+    }
+}
+
 impl<const S: usize> Solvable for Board<S>
 where
     [(); board_area::<S>()]: Sized,
@@ -81,75 +125,242 @@ where
     type Step = MatchSet;
 
     fn is_goal(&self) -> bool {
-        self.is_empty()
+        ClassicRules::default().is_won(self)
     }
 
     fn apply_step(&self, step: &Self::Step) -> Self {
         self.without_match_set(step)
     }
 
+    fn is_dead_end(&self) -> bool {
+        self.is_provably_unsolvable() || !self.dead_tiles().is_empty()
+    }
+
+    fn canonicalize(&self) -> Self {
+        self.canonical()
+    }
+
+    fn forced_step(&self) -> Option<Self::Step> {
+        self.forced_moves().into_iter().next()
+    }
+
+    fn step_priority(&self, step: &Self::Step) -> Option<u32> {
+        Some(self.move_priority(step, &PriorityWeights::default()))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn next_steps(&self) -> Vec<Self::Step> {
-        let mut steps: Vec<MatchSet> = self.find_match_sets().into_iter().collect();
+        self.prioritized_steps(&PriorityWeights::default())
+    }
+}
+
+/// How strongly the solver prefers each kind of move. Lower weights are tried first.
+///
+/// The defaults are the heuristic the solver has always used, with 50 as a neutral value. Moves
+/// clearing one kind of tile take their weight from [`Tile::base_priority`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PriorityWeights {
+    pub gold: u32,
+    pub metal: u32,
+    /// Weight of an element match that leaves more of that element on the board.
+    pub element: u32,
+    /// Weight of an element match that clears the last pair of that element.
+    pub last_element_pair: u32,
+    pub duality: u32,
+    pub theta_theta: u32,
+    pub element_theta: u32,
+    pub unknown: u32,
+}
+impl Default for PriorityWeights {
+    fn default() -> Self {
+        Self::for_rules(&ClassicRules::default())
+    }
+}
+impl PriorityWeights {
+    /// The default weights, with moves of one kind of tile weighted by
+    /// [`Ruleset::base_priority`].
+    pub fn for_rules(rules: &impl Ruleset) -> Self {
+        let base = |tile| rules.base_priority(tile).unwrap_or(50);
+        Self {
+            gold: base(Tile::Gold),
+            metal: base(Tile::Quicksilver),
+            element: base(Tile::Element(ElementTile::Fire)),
+            last_element_pair: 20,
+            duality: base(Tile::Binary(BinaryTile::Life)),
+            theta_theta: base(Tile::Theta),
+            // Don't prefer this, since it opens us up to holes.
+            element_theta: 100,
+            unknown: 51,
+        }
+    }
+}
 
-        // Determine priority for making specific moves (lower is tried first).
-        // Using 50 as a neutral value.
-        steps.sort_by_key(|step| {
-            match MoveType::identify(self, step) {
-                // Always go for gold.
-                MoveType::Gold => 0,
-                MoveType::Metal => 50,
-                // Go for element match if it's the last pair
-                MoveType::Element => {
-                    let element_coord = step.iter().next().unwrap();
-                    let element_tile = self.get_tile(element_coord);
-                    let elements_left = self
-                        .nonempty_tiles()
-                        .filter(|(_, t)| t == &element_tile)
-                        .count();
-                    if elements_left <= 2 { 20 } else { 50 }
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// The weight of making `step` on this board (lower is tried first).
+    pub fn move_priority(&self, step: &MatchSet, weights: &PriorityWeights) -> u32 {
+        self.move_priority_with_census(step, weights, &self.census())
+    }
+    fn move_priority_with_census(
+        &self,
+        step: &MatchSet,
+        weights: &PriorityWeights,
+        census: &TileCensus,
+    ) -> u32 {
+        match MoveType::identify(self, step) {
+            MoveType::Gold => weights.gold,
+            MoveType::Metal => weights.metal,
+            MoveType::Element => {
+                let element_coord = step.iter().next().unwrap();
+                let elements_left = census.count(*self.get_tile(element_coord));
+                if elements_left <= 2 {
+                    weights.last_element_pair
+                } else {
+                    weights.element
                 }
-                MoveType::Duality => 50,
-                MoveType::Unknown => 51,
-                MoveType::ThetaTheta => 75,
-                // Don't prefer this, since it opens us up to holes.
-                MoveType::ElementTheta => 100,
             }
-        });
+            MoveType::Duality => weights.duality,
+            MoveType::Unknown => weights.unknown,
+            MoveType::ThetaTheta => weights.theta_theta,
+            MoveType::ElementTheta => weights.element_theta,
+        }
+    }
 
+    /// List every available move, ordered by `weights`.
+    pub fn prioritized_steps(&self, weights: &PriorityWeights) -> Vec<MatchSet> {
+        let mut steps: Vec<MatchSet> = self.find_match_sets().into_iter().collect();
+        // Break ties by coordinates, so equally weighted moves keep the match set order.
+        let census = self.census();
+        steps.sort_by_cached_key(|step| {
+            (
+                self.move_priority_with_census(step, weights, &census),
+                step.clone(),
+            )
+        });
         steps
     }
 }
 
-pub fn solve_dfs<G: Solvable>(board: &G) -> Option<Vec<G::Step>> {
-    let mut seen = HashSet::new();
-    let mut path = Vec::new();
+/// A snapshot of a running search, passed to progress callbacks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressEvent {
+    /// Number of distinct states visited so far.
+    pub states_explored: usize,
+    /// Average number of states visited per second since the search started.
+    pub states_per_sec: f64,
+    /// Number of moves on the path currently being explored.
+    pub depth: usize,
+    /// Deepest path reached so far.
+    pub max_depth: usize,
+    /// Time since the search started.
+    pub elapsed: Duration,
+}
 
-    dfs(board, &mut path, &mut seen);
+/// Search for a solution with the default [`SolverOptions`](crate::SolverOptions).
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn solve_dfs<G: Solvable>(board: &G) -> SolveOutcome<G> {
+    Solver::default().solve(board)
+}
 
-    Some(path)
+/// The result of a full search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveOutcome<G: Solvable> {
+    /// A solution was found.
+    Solved(Vec<G::Step>),
+    /// Every reachable state was explored without finding a solution.
+    ///
+    /// When a move bound is set, this only proves there is no solution within the bound.
+    Unsolvable {
+        /// Number of distinct states visited before the search was exhausted.
+        states_explored: usize,
+        /// Number of visited states pruned because [`Solvable::is_dead_end`] proved them unsolvable.
+        dead_ends_pruned: usize,
+    },
+    /// The search ran out of time before finding a solution.
+    TimedOut,
+    /// The search was stopped through a [`CancelToken`] before finding a solution.
+    Cancelled,
+}
+impl<G: Solvable> SolveOutcome<G> {
+    /// Interpret the result of a search run, using `cancel` to tell why a search stopped early.
+    pub(crate) fn from_search(
+        progress: SearchProgress<G>,
+        search: &SearchState<G>,
+        cancel: Option<&CancelToken>,
+    ) -> Self {
+        match progress {
+            SearchProgress::Solved(solution) => SolveOutcome::Solved(solution),
+            SearchProgress::Exhausted => SolveOutcome::Unsolvable {
+                states_explored: search.states_explored(),
+                dead_ends_pruned: search.dead_ends(),
+            },
+            SearchProgress::Paused if cancel.is_some_and(CancelToken::is_cancelled) => {
+                SolveOutcome::Cancelled
+            }
+            SearchProgress::Paused => SolveOutcome::TimedOut,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        matches!(self, SolveOutcome::Solved(_))
+    }
+    /// The solution, if one was found.
+    pub fn into_solution(self) -> Option<Vec<G::Step>> {
+        match self {
+            SolveOutcome::Solved(solution) => Some(solution),
+            _ => None,
+        }
+    }
 }
 
-fn dfs<G: Solvable>(
-    game: &G,
-    path: &mut Vec<G::Step>,
-    seen: &mut HashSet<G>,
-) -> Option<Vec<G::Step>> {
-    if game.is_goal() {
-        return Some(path.clone());
+/// A flag which can be shared with running searches to stop them early.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Stop every search sharing this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
     }
-    // Prune if we've seen this board before.
-    if seen.contains(game) {
-        return None;
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
     }
-    seen.insert(game.clone());
+}
 
-    for neighbor in game.next_steps() {
-        let next_board = game.apply_step(&neighbor);
-        path.push(neighbor);
-        if let Some(solution) = dfs(&next_board, path, seen) {
-            return Some(solution);
+/// The result of [`solve_greedy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GreedyOutcome<G: Solvable> {
+    /// The greedy line of play reached a goal state.
+    Solved(Vec<G::Step>),
+    /// The greedy line of play ran out of moves before reaching a goal state.
+    Stuck {
+        /// The steps that were taken before getting stuck.
+        steps: Vec<G::Step>,
+        /// The state that has no further steps.
+        state: G,
+    },
+}
+
+/// Always take the highest-priority step, without ever backtracking.
+///
+/// This is much faster than a full search, but can get stuck on boards that a full search would solve.
+pub fn solve_greedy<G: Solvable>(board: &G) -> GreedyOutcome<G> {
+    let mut state = board.clone();
+    let mut steps = Vec::new();
+    while !state.is_goal() {
+        match state.next_steps().into_iter().next() {
+            Some(step) => {
+                state = state.apply_step(&step);
+                steps.push(step);
+            }
+            None => return GreedyOutcome::Stuck { steps, state },
         }
-        path.pop();
     }
-    None
+    GreedyOutcome::Solved(steps)
 }
@@ -0,0 +1,505 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use std::{fmt, thread};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::board::Board;
+use crate::coord::MatchSet;
+use crate::math::board_area;
+use crate::search::{RunOptions, SearchProgress, SearchState, StepOrder, TraceEvent};
+use crate::solve::{
+    CancelToken, GreedyOutcome, PriorityWeights, ProgressEvent, Solvable, SolveOutcome,
+    solve_greedy,
+};
+
+/// The order in which a solver tries the moves available from each state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoveOrdering {
+    /// Try moves in the priority order given by [`Solvable::next_steps`].
+    #[default]
+    Priority,
+    /// Try moves in a random order, shuffled by an RNG seeded with `seed`.
+    Shuffled { seed: u64 },
+    /// Try moves in priority order, shuffling moves of equal [`Solvable::step_priority`] with an RNG
+    /// seeded with `seed`, and start over with a fresh shuffle after `restart_after` backtracks.
+    ///
+    /// The backtrack allowance doubles on every restart, so the search still finishes on boards
+    /// with no solution.
+    Restarts { seed: u64, restart_after: usize },
+}
+
+/// Which states a solver forgets first once its seen set reaches the configured limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// Forget the deepest states first, since shallow states prune the most work when seen again.
+    #[default]
+    KeepShallowest,
+    /// Forget the states which were least recently reached.
+    LeastRecentlyUsed,
+}
+impl fmt::Display for EvictionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EvictionPolicy::KeepShallowest => "keep_shallowest",
+            EvictionPolicy::LeastRecentlyUsed => "least_recently_used",
+        })
+    }
+}
+impl FromStr for EvictionPolicy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep_shallowest" => Ok(EvictionPolicy::KeepShallowest),
+            "least_recently_used" => Ok(EvictionPolicy::LeastRecentlyUsed),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One of the strategies raced by [`Solver::solve_portfolio`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Always take the highest-priority step. See [`solve_greedy`].
+    Greedy,
+    /// A full depth-first search trying moves in the given order.
+    Search(MoveOrdering),
+}
+
+/// Seed for the shuffled search in a portfolio, unless the options already ask for a shuffled ordering.
+const PORTFOLIO_SEED: u64 = 0x5eed;
+
+/// How often a portfolio checks the caller's cancel token while waiting on its strategies.
+const PORTFOLIO_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Tuning knobs for a [`Solver`].
+///
+/// Options are set by chaining `with_*` methods onto [`SolverOptions::new`].
+#[derive(Debug, Clone)]
+pub struct SolverOptions {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) threads: Option<usize>,
+    pub(crate) ordering: MoveOrdering,
+    pub(crate) max_moves: Option<usize>,
+    pub(crate) prune_dead_ends: bool,
+    pub(crate) use_symmetry: bool,
+    pub(crate) seen_limit: Option<usize>,
+    pub(crate) eviction: EvictionPolicy,
+    pub(crate) cancel: Option<CancelToken>,
+}
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            threads: None,
+            ordering: MoveOrdering::default(),
+            max_moves: None,
+            prune_dead_ends: true,
+            use_symmetry: true,
+            seen_limit: None,
+            eviction: EvictionPolicy::default(),
+            cancel: None,
+        }
+    }
+}
+impl SolverOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give up on a board after searching for `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Number of worker threads used when solving many boards. Defaults to the available parallelism.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+    pub fn with_ordering(mut self, ordering: MoveOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+    /// Only accept solutions of at most `max_moves` moves.
+    pub fn with_max_moves(mut self, max_moves: usize) -> Self {
+        self.max_moves = Some(max_moves);
+        self
+    }
+    /// Whether to prune states that [`Solvable::is_dead_end`] proves unsolvable. Enabled by default.
+    pub fn with_dead_end_pruning(mut self, enabled: bool) -> Self {
+        self.prune_dead_ends = enabled;
+        self
+    }
+    /// Whether to treat states with the same [`Solvable::canonicalize`] form as already seen.
+    /// Enabled by default.
+    pub fn with_symmetry_reduction(mut self, enabled: bool) -> Self {
+        self.use_symmetry = enabled;
+        self
+    }
+    /// Remember at most `limit` visited states, forgetting some according to `policy` when full.
+    ///
+    /// Forgotten states may be searched again, trading time for a fixed memory footprint.
+    pub fn with_seen_limit(mut self, limit: usize, policy: EvictionPolicy) -> Self {
+        self.seen_limit = Some(limit);
+        self.eviction = policy;
+        self
+    }
+    /// Stop searching once `cancel` is cancelled.
+    pub fn with_cancel_token(mut self, cancel: CancelToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+}
+
+/// Scores a state for a [`Heuristics`] hook.
+type Evaluation<'a, G> = Box<dyn Fn(&G) -> u32 + 'a>;
+
+/// Caller-supplied state evaluations which steer a [`Solver`] without wrapping the state type.
+///
+/// Evaluations are set by chaining methods onto [`Heuristics::new`].
+pub struct Heuristics<'a, G> {
+    order_by: Option<Evaluation<'a, G>>,
+    prune_above: Option<(u32, Evaluation<'a, G>)>,
+}
+impl<G> Default for Heuristics<'_, G> {
+    fn default() -> Self {
+        Self {
+            order_by: None,
+            prune_above: None,
+        }
+    }
+}
+impl<'a, G> Heuristics<'a, G> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Try the steps leading to states with the lowest `evaluate` score first.
+    /// Steps with equal scores keep their priority order.
+    pub fn order_by(mut self, evaluate: impl Fn(&G) -> u32 + 'a) -> Self {
+        self.order_by = Some(Box::new(evaluate));
+        self
+    }
+    /// Don't expand states whose `evaluate` score is above `limit`.
+    pub fn prune_above(mut self, limit: u32, evaluate: impl Fn(&G) -> u32 + 'a) -> Self {
+        self.prune_above = Some((limit, Box::new(evaluate)));
+        self
+    }
+}
+
+/// A configured depth-first solver.
+#[derive(Debug, Clone, Default)]
+pub struct Solver {
+    options: SolverOptions,
+}
+impl Solver {
+    pub fn new(options: SolverOptions) -> Self {
+        Self { options }
+    }
+    pub fn options(&self) -> &SolverOptions {
+        &self.options
+    }
+
+    pub fn solve<G: Solvable>(&self, board: &G) -> SolveOutcome<G> {
+        self.run(board, None, None, None, None)
+    }
+
+    /// Same as [`Solver::solve`], but steered by the evaluations in `heuristics`.
+    ///
+    /// A shuffled or restarting [`MoveOrdering`] still takes precedence over [`Heuristics::order_by`].
+    pub fn solve_with_heuristics<G: Solvable>(
+        &self,
+        board: &G,
+        heuristics: &Heuristics<G>,
+    ) -> SolveOutcome<G> {
+        let mut reorder = heuristics.order_by.as_ref().map(|evaluate| {
+            move |state: &G, steps: &mut Vec<G::Step>| {
+                steps.sort_by_cached_key(|step| evaluate(&state.apply_step(step)))
+            }
+        });
+        let prune = heuristics
+            .prune_above
+            .as_ref()
+            .map(|(limit, evaluate)| move |state: &G| evaluate(state) > *limit);
+        self.run(
+            board,
+            None,
+            reorder.as_mut().map(|f| f as &mut StepOrder<G>),
+            prune.as_ref().map(|f| f as &dyn Fn(&G) -> bool),
+            None,
+        )
+    }
+
+    /// Same as [`Solver::solve`], but orders moves by `weights` instead of the default priorities.
+    ///
+    /// A shuffled or restarting [`MoveOrdering`] still takes precedence over the weights.
+    pub fn solve_weighted<const S: usize>(
+        &self,
+        board: &Board<S>,
+        weights: &PriorityWeights,
+    ) -> SolveOutcome<Board<S>>
+    where
+        [(); board_area::<S>()]: Sized,
+    {
+        let mut reorder = |board: &Board<S>, steps: &mut Vec<MatchSet>| {
+            steps.sort_by_key(|step| board.move_priority(step, weights))
+        };
+        self.run(board, None, Some(&mut reorder), None, None)
+    }
+
+    /// Same as [`Solver::solve`], but also records every decision the search makes.
+    ///
+    /// The trace grows with every state visited, so this is meant for debugging small searches.
+    pub fn solve_traced<G: Solvable>(&self, board: &G) -> (SolveOutcome<G>, Vec<TraceEvent<G>>) {
+        let mut trace = Vec::new();
+        let outcome = self.run(board, None, None, None, Some(&mut trace));
+        (outcome, trace)
+    }
+
+    /// Same as [`Solver::solve`], but periodically reports search progress to `on_progress`.
+    pub fn solve_with_progress<G: Solvable>(
+        &self,
+        board: &G,
+        mut on_progress: impl FnMut(ProgressEvent),
+    ) -> SolveOutcome<G> {
+        self.run(board, Some(&mut on_progress), None, None, None)
+    }
+
+    /// Solve every board in `boards` on a pool of worker threads, applying the timeout to each board.
+    ///
+    /// Outcomes are returned in the same order as `boards`.
+    pub fn solve_many<G>(&self, boards: &[G]) -> Vec<SolveOutcome<G>>
+    where
+        G: Solvable + Sync,
+        G::Step: Send,
+    {
+        let threads = self
+            .options
+            .threads
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+            .clamp(1, boards.len().max(1));
+        let next_board = AtomicUsize::new(0);
+
+        let mut outcomes: Vec<(usize, SolveOutcome<G>)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut solved = Vec::new();
+                        loop {
+                            let idx = next_board.fetch_add(1, Ordering::Relaxed);
+                            let Some(board) = boards.get(idx) else {
+                                break solved;
+                            };
+                            solved.push((idx, self.solve(board)));
+                        }
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("solver worker panicked"))
+                .collect()
+        });
+
+        outcomes.sort_by_key(|(idx, _)| *idx);
+        outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+
+    /// Race a greedy solve, a priority-ordered search, and a shuffled search on separate threads,
+    /// returning the first conclusive outcome along with the strategy that produced it.
+    ///
+    /// A greedy solve that gets stuck proves nothing, so it only wins by finding a solution.
+    /// The remaining strategies are cancelled once one of them wins.
+    pub fn solve_portfolio<G>(&self, board: &G) -> (Strategy, SolveOutcome<G>)
+    where
+        G: Solvable + Sync,
+        G::Step: Send,
+    {
+        let shuffled = match self.options.ordering {
+            MoveOrdering::Shuffled { seed } | MoveOrdering::Restarts { seed, .. } => {
+                MoveOrdering::Shuffled { seed }
+            }
+            MoveOrdering::Priority => MoveOrdering::Shuffled {
+                seed: PORTFOLIO_SEED,
+            },
+        };
+        let searches = [MoveOrdering::Priority, shuffled];
+        let stop = CancelToken::new();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            let greedy = sender.clone();
+            scope.spawn(move || {
+                let outcome = match solve_greedy(board) {
+                    GreedyOutcome::Solved(steps) => Some(SolveOutcome::Solved(steps)),
+                    GreedyOutcome::Stuck { .. } => None,
+                };
+                let _ = greedy.send((Strategy::Greedy, outcome));
+            });
+            for ordering in searches {
+                let sender = sender.clone();
+                let solver = Solver::new(
+                    self.options
+                        .clone()
+                        .with_ordering(ordering)
+                        .with_cancel_token(stop.clone()),
+                );
+                scope.spawn(move || {
+                    let _ = sender.send((Strategy::Search(ordering), Some(solver.solve(board))));
+                });
+            }
+            drop(sender);
+
+            let mut fallback = (
+                Strategy::Search(MoveOrdering::Priority),
+                SolveOutcome::TimedOut,
+            );
+            loop {
+                match receiver.recv_timeout(PORTFOLIO_POLL_INTERVAL) {
+                    Ok((strategy, Some(outcome))) => match outcome {
+                        SolveOutcome::Solved(_) | SolveOutcome::Unsolvable { .. } => {
+                            stop.cancel();
+                            break (strategy, outcome);
+                        }
+                        SolveOutcome::TimedOut | SolveOutcome::Cancelled => {
+                            fallback = (strategy, outcome)
+                        }
+                    },
+                    Ok((_, None)) => {}
+                    Err(RecvTimeoutError::Timeout) => {
+                        if self
+                            .options
+                            .cancel
+                            .as_ref()
+                            .is_some_and(CancelToken::is_cancelled)
+                        {
+                            stop.cancel();
+                        }
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break fallback,
+                }
+            }
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", name = "solve", skip_all, fields(ordering = ?self.options.ordering))
+    )]
+    fn run<G: Solvable>(
+        &self,
+        board: &G,
+        on_progress: Option<&mut dyn FnMut(ProgressEvent)>,
+        reorder: Option<&mut StepOrder<G>>,
+        prune: Option<&dyn Fn(&G) -> bool>,
+        trace: Option<&mut Vec<TraceEvent<G>>>,
+    ) -> SolveOutcome<G> {
+        let (mut shuffle, mut restart_after) = match self.options.ordering {
+            MoveOrdering::Priority => (None, None),
+            MoveOrdering::Shuffled { seed } => (Some((StdRng::seed_from_u64(seed), false)), None),
+            MoveOrdering::Restarts {
+                seed,
+                restart_after,
+            } => (
+                Some((StdRng::seed_from_u64(seed), true)),
+                Some(restart_after.max(1)),
+            ),
+        };
+        let mut shuffle = shuffle.as_mut().map(|(rng, ties_only)| {
+            move |state: &G, steps: &mut Vec<G::Step>| {
+                if *ties_only {
+                    shuffle_ties(state, steps, rng)
+                } else {
+                    steps.shuffle(rng)
+                }
+            }
+        });
+        let (mut on_progress, mut reorder, mut trace) = (on_progress, reorder, trace);
+        let deadline = self.options.timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let mut search = SearchState::new(board, &self.options);
+            let progress = search.run(RunOptions {
+                deadline,
+                cancel: self.options.cancel.as_ref(),
+                max_backtracks: restart_after,
+                // Reborrow so the callbacks' lifetimes can shrink to match the shuffle's.
+                on_progress: on_progress
+                    .as_deref_mut()
+                    .map(|f| f as &mut dyn FnMut(ProgressEvent)),
+                order: match shuffle.as_mut() {
+                    Some(shuffle) => Some(shuffle as &mut StepOrder<G>),
+                    None => reorder.as_deref_mut().map(|f| f as &mut StepOrder<G>),
+                },
+                prune,
+                trace: trace.as_deref_mut(),
+                ..Default::default()
+            });
+
+            let stopped = deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                || self
+                    .options
+                    .cancel
+                    .as_ref()
+                    .is_some_and(CancelToken::is_cancelled);
+            match restart_after.as_mut() {
+                Some(restart_after) if matches!(progress, SearchProgress::Paused) && !stopped => {
+                    *restart_after = restart_after.saturating_mul(2);
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(restart_after = *restart_after, "restarting search");
+                }
+                _ => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        states_explored = search.states_explored(),
+                        dead_ends = search.dead_ends(),
+                        "search finished"
+                    );
+                    return SolveOutcome::from_search(
+                        progress,
+                        &search,
+                        self.options.cancel.as_ref(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Shuffle runs of steps which have the same [`Solvable::step_priority`], keeping the runs in order.
+fn shuffle_ties<G: Solvable>(state: &G, steps: &mut [G::Step], rng: &mut StdRng) {
+    let priorities: Vec<Option<u32>> = steps.iter().map(|step| state.step_priority(step)).collect();
+    let mut start = 0;
+    while start < steps.len() {
+        let len = priorities[start..]
+            .iter()
+            .take_while(|priority| **priority == priorities[start])
+            .count();
+        steps[start..start + len].shuffle(rng);
+        start += len;
+    }
+}
+
+/// Solve every board in `boards` on a pool of worker threads. See [`Solver::solve_many`].
+pub fn solve_many<G>(boards: &[G], options: &SolverOptions) -> Vec<SolveOutcome<G>>
+where
+    G: Solvable + Sync,
+    G::Step: Send,
+{
+    Solver::new(options.clone()).solve_many(boards)
+}
+
+/// Race several strategies on `board` with the default options. See [`Solver::solve_portfolio`].
+pub fn solve_portfolio<G>(board: &G) -> (Strategy, SolveOutcome<G>)
+where
+    G: Solvable + Sync,
+    G::Step: Send,
+{
+    Solver::default().solve_portfolio(board)
+}
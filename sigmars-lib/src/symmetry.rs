@@ -0,0 +1,207 @@
+use crate::board::Board;
+use crate::coord::BoardCoord;
+use crate::math::board_area;
+
+/// One of the 12 symmetries of a hexagonal board: a rotation by a multiple of 60°,
+/// optionally preceded by a reflection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symmetry {
+    /// Number of clockwise 60° rotations, in `0..6`.
+    rotation: u8,
+    /// Whether the board is reflected before rotating.
+    reflected: bool,
+}
+impl Symmetry {
+    /// Every symmetry, starting with the identity.
+    pub const ALL: [Symmetry; 12] = {
+        let mut all = [Symmetry {
+            rotation: 0,
+            reflected: false,
+        }; 12];
+        let mut i = 0;
+        while i < 12 {
+            all[i] = Symmetry {
+                rotation: (i % 6) as u8,
+                reflected: i >= 6,
+            };
+            i += 1;
+        }
+        all
+    };
+    /// The reflection which reverses each row, swapping left and right.
+    pub const MIRROR_HORIZONTAL: Symmetry = Symmetry::new(2, true);
+    /// The reflection which reverses the order of the rows, swapping top and bottom.
+    pub const MIRROR_VERTICAL: Symmetry = Symmetry::new(5, true);
+
+    /// The symmetry rotating `rotation` times by 60° clockwise, after reflecting if `reflected`.
+    pub const fn new(rotation: u8, reflected: bool) -> Self {
+        Self {
+            rotation: rotation % 6,
+            reflected,
+        }
+    }
+    /// Number of clockwise 60° rotations, in `0..6`.
+    pub fn rotation(&self) -> u8 {
+        self.rotation
+    }
+    pub fn is_reflected(&self) -> bool {
+        self.reflected
+    }
+
+    /// Map axial `(q, r)` coordinates through this symmetry.
+    fn apply_axial(&self, (mut q, mut r): (isize, isize)) -> (isize, isize) {
+        if self.reflected {
+            (q, r) = (r, q);
+        }
+        for _ in 0..self.rotation {
+            (q, r) = (-r, q + r);
+        }
+        (q, r)
+    }
+
+    /// Map a board coordinate through this symmetry.
+    pub fn apply<const S: usize>(&self, coord: BoardCoord) -> BoardCoord {
+        let (q, r) = self.apply_axial(coord.to_axial::<S>());
+        BoardCoord::from_axial::<S>(q, r).expect("hex symmetries keep coordinates on the board")
+    }
+}
+
+impl<const S: usize> Board<S>
+where
+    [(); board_area::<S>()]: Sized,
+{
+    /// Return this board with every tile moved through `symmetry`.
+    pub fn transformed(&self, symmetry: Symmetry) -> Self {
+        self.permuted(|idx| {
+            symmetry
+                .apply::<S>(BoardCoord::from_index::<S>(idx))
+                .as_index::<S>()
+        })
+    }
+
+    /// Return this board flipped left to right.
+    pub fn mirror_horizontal(&self) -> Self {
+        self.transformed(Symmetry::MIRROR_HORIZONTAL)
+    }
+    /// Return this board flipped top to bottom.
+    pub fn mirror_vertical(&self) -> Self {
+        self.transformed(Symmetry::MIRROR_VERTICAL)
+    }
+
+    /// The symmetry which maps this board onto `other`, if they are symmetric variants of each other.
+    /// Identical boards give the identity.
+    pub fn eq_up_to_symmetry(&self, other: &Self) -> Option<Symmetry> {
+        Symmetry::ALL
+            .into_iter()
+            .find(|symmetry| self.transformed(*symmetry) == *other)
+    }
+
+    /// Return the lexicographically smallest board among all 12 rotations and reflections of
+    /// this board, so boards which are symmetric variants of each other compare equal.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+    pub fn canonical(&self) -> Self {
+        Symmetry::ALL
+            .iter()
+            .map(|symmetry| self.transformed(*symmetry))
+            .min_by(|a, b| a.tiles().cmp(b.tiles()))
+            .expect("there is always at least the identity symmetry")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::{ElementTile, Tile};
+
+    #[test]
+    fn test_symmetries_are_permutations() {
+        for symmetry in Symmetry::ALL {
+            let mut seen = [false; board_area::<4>()];
+            for idx in 0..board_area::<4>() {
+                let coord = symmetry.apply::<4>(BoardCoord::from_index::<4>(idx));
+                seen[coord.as_index::<4>()] = true;
+            }
+            assert!(
+                seen.iter().all(|s| *s),
+                "{:?} is not a permutation",
+                symmetry
+            );
+        }
+    }
+
+    #[test]
+    fn test_mirrors() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(1, 1), Tile::Gold),
+            (BoardCoord::new(2, 4), Tile::Theta),
+        ]);
+
+        let horizontal = board.mirror_horizontal();
+        for (row, mirrored) in board.rows().zip(horizontal.rows()) {
+            assert!(row.iter().eq(mirrored.iter().rev()));
+        }
+        let vertical = board.mirror_vertical();
+        let mut rows: Vec<&[Tile]> = vertical.rows().collect();
+        rows.reverse();
+        assert!(board.rows().eq(rows));
+
+        assert_eq!(horizontal.mirror_horizontal(), board);
+        assert_eq!(vertical.mirror_vertical(), board);
+    }
+
+    #[test]
+    fn test_six_rotations_are_identity() {
+        let rotation = Symmetry {
+            rotation: 1,
+            reflected: false,
+        };
+        let coord = BoardCoord::new(0, 1);
+        let rotated = (0..6).fold(coord, |c, _| rotation.apply::<3>(c));
+        assert_eq!(rotated, coord);
+    }
+
+    #[test]
+    fn test_corners_share_canonical_form() {
+        let corners = [
+            BoardCoord::new(0, 0),
+            BoardCoord::new(0, 2),
+            BoardCoord::new(2, 0),
+            BoardCoord::new(2, 4),
+            BoardCoord::new(4, 0),
+            BoardCoord::new(4, 2),
+        ];
+        let canonical = corners
+            .map(|c| Board::<3>::from_iter([(c, Tile::Element(ElementTile::Fire))]).canonical());
+        assert!(canonical.iter().all(|b| *b == canonical[0]));
+    }
+
+    #[test]
+    fn test_mirrored_boards_share_canonical_form() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 1), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(1, 3), Tile::Gold),
+        ]);
+        let mirrored = board.transformed(Symmetry::new(0, true));
+        assert_ne!(mirrored, board);
+        assert_eq!(mirrored.canonical(), board.canonical());
+    }
+
+    #[test]
+    fn test_eq_up_to_symmetry() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(0, 1), Tile::Element(ElementTile::Water)),
+        ]);
+        let symmetry = Symmetry::new(2, true);
+        let other = board.transformed(symmetry);
+
+        assert_eq!(
+            board.eq_up_to_symmetry(&board),
+            Some(Symmetry::new(0, false))
+        );
+        assert_eq!(board.eq_up_to_symmetry(&other), Some(symmetry));
+        assert_eq!(board.eq_up_to_symmetry(&Board::empty()), None);
+    }
+}
@@ -1,7 +1,12 @@
+use rand::Rng;
+
 use crate::board::Board;
-use crate::coord::{BoardCoord, MatchSet, MatchSets};
-use crate::errors::BoardParseError;
+use crate::census::TileCensus;
+use crate::coord::{BoardCoord, MatchSets};
+use crate::errors::{BoardParseError, TileParseError};
 use crate::math::board_area;
+use crate::multiset::TileMultiset;
+use crate::ruleset::{ClassicRules, Ruleset, matches_for};
 
 pub trait Matchable {
     fn filter_matches<'a, const S: usize, I>(
@@ -15,32 +20,25 @@ pub trait Matchable {
         [(); board_area::<S>()]:;
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElementTile {
     Air,
     Fire,
     Water,
     Earth,
 }
-impl Matchable for ElementTile {
-    fn filter_matches<'a, const S: usize, I>(
-        &self,
-        coord: &BoardCoord,
-        _board: &'a Board<S>,
-        candidates: I,
-    ) -> MatchSets
-    where
-        I: Iterator<Item = (BoardCoord, &'a Tile)>,
-        [(); board_area::<S>()]:,
-    {
-        candidates
-            .filter(|(c, t)| matches!(t, Tile::Element(e) if e == self && c != coord))
-            .map(|(c, _)| MatchSet::from([*coord, c]))
-            .collect()
-    }
+impl ElementTile {
+    pub const ALL: [ElementTile; 4] = [
+        ElementTile::Air,
+        ElementTile::Fire,
+        ElementTile::Water,
+        ElementTile::Earth,
+    ];
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MetalTile {
     Lead = 0,
     Tin = 1,
@@ -48,34 +46,25 @@ pub enum MetalTile {
     Copper = 3,
     Silver = 4,
 }
+impl MetalTile {
+    /// Every metal, in the order they must be projected.
+    pub const ALL: [MetalTile; 5] = [
+        MetalTile::Lead,
+        MetalTile::Tin,
+        MetalTile::Iron,
+        MetalTile::Copper,
+        MetalTile::Silver,
+    ];
+}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryTile {
     Life,
     Death,
 }
-impl Matchable for BinaryTile {
-    fn filter_matches<'a, const S: usize, I>(
-        &self,
-        coord: &BoardCoord,
-        _board: &'a Board<S>,
-        tiles: I,
-    ) -> MatchSets
-    where
-        I: Iterator<Item = (BoardCoord, &'a Tile)>,
-        [(); board_area::<S>()]:,
-    {
-        tiles
-            .filter_map(|(c, t)| match t {
-                Tile::Binary(e) if e != self => Some(c),
-                _ => None,
-            })
-            .map(|c| MatchSet::from([*coord, c]))
-            .collect()
-    }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Tile {
     Empty,
     Theta,
@@ -84,7 +73,90 @@ pub enum Tile {
     Quicksilver,
     Metal(MetalTile),
     Gold,
+    /// A permanent obstacle for custom puzzles. It fills its cell but is never selectable, never
+    /// matches, and is not left to clear.
+    Blocked,
+    /// Matches one of each of the four elements at once, in expanded rulesets.
+    #[cfg(feature = "quintessence")]
+    Quintessence,
+}
+impl Tile {
+    /// Every tile, including empty and blocked cells, in sorted order.
+    pub const ALL: &'static [Tile] = &[
+        Tile::Empty,
+        Tile::Theta,
+        Tile::Element(ElementTile::Air),
+        Tile::Element(ElementTile::Fire),
+        Tile::Element(ElementTile::Water),
+        Tile::Element(ElementTile::Earth),
+        Tile::Binary(BinaryTile::Life),
+        Tile::Binary(BinaryTile::Death),
+        Tile::Quicksilver,
+        Tile::Metal(MetalTile::Lead),
+        Tile::Metal(MetalTile::Tin),
+        Tile::Metal(MetalTile::Iron),
+        Tile::Metal(MetalTile::Copper),
+        Tile::Metal(MetalTile::Silver),
+        Tile::Gold,
+        Tile::Blocked,
+        #[cfg(feature = "quintessence")]
+        Tile::Quintessence,
+    ];
+}
+
+/// Every classic tile, indexed by its [`Tile::to_u8`] code.
+const TILE_CODES: [Tile; 15] = [
+    Tile::Empty,
+    Tile::Theta,
+    Tile::Element(ElementTile::Air),
+    Tile::Element(ElementTile::Fire),
+    Tile::Element(ElementTile::Water),
+    Tile::Element(ElementTile::Earth),
+    Tile::Binary(BinaryTile::Life),
+    Tile::Binary(BinaryTile::Death),
+    Tile::Quicksilver,
+    Tile::Metal(MetalTile::Lead),
+    Tile::Metal(MetalTile::Tin),
+    Tile::Metal(MetalTile::Iron),
+    Tile::Metal(MetalTile::Copper),
+    Tile::Metal(MetalTile::Silver),
+    Tile::Gold,
+];
+/// Code used for quintessence, after every classic tile.
+#[cfg(feature = "quintessence")]
+const QUINTESSENCE_CODE: u8 = 15;
+/// Code used for blocked cells, the first that doesn't fit in four bits.
+const BLOCKED_CODE: u8 = 16;
+
+impl Tile {
+    /// A stable numeric code for this tile, as used by the binary board format.
+    ///
+    /// Classic tiles fit in four bits: empty 0, salt 1, air 2, fire 3, water 4, earth 5, vitae 6,
+    /// mors 7, quicksilver 8, lead 9, tin 10, iron 11, copper 12, silver 13, and gold 14. Then
+    /// quintessence is 15 and blocked is 16. Codes are never reused or renumbered.
+    pub fn to_u8(self) -> u8 {
+        #[cfg(feature = "quintessence")]
+        if self == Tile::Quintessence {
+            return QUINTESSENCE_CODE;
+        }
+        if self == Tile::Blocked {
+            return BLOCKED_CODE;
+        }
+        TILE_CODES.iter().position(|t| *t == self).unwrap() as u8
+    }
+    /// The tile with the given [`Tile::to_u8`] code.
+    pub fn from_u8(code: u8) -> Option<Tile> {
+        #[cfg(feature = "quintessence")]
+        if code == QUINTESSENCE_CODE {
+            return Some(Tile::Quintessence);
+        }
+        if code == BLOCKED_CODE {
+            return Some(Tile::Blocked);
+        }
+        TILE_CODES.get(code as usize).copied()
+    }
 }
+
 impl TryFrom<char> for Tile {
     type Error = BoardParseError;
 
@@ -105,70 +177,266 @@ impl TryFrom<char> for Tile {
             '3' => Ok(Tile::Metal(MetalTile::Copper)),
             '4' => Ok(Tile::Metal(MetalTile::Silver)),
             '5' => Ok(Tile::Gold),
+            'B' => Ok(Tile::Blocked),
+            #[cfg(feature = "quintessence")]
+            'X' => Ok(Tile::Quintessence),
             _ => Err(BoardParseError::UnexpectedTileCharacter(value)),
         }
     }
 }
 
+impl Tile {
+    /// The character used for this tile in the board text format, the inverse of its
+    /// `TryFrom<char>` conversion.
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Empty => '_',
+            Tile::Element(ElementTile::Fire) => 'F',
+            Tile::Element(ElementTile::Water) => 'W',
+            Tile::Element(ElementTile::Air) => 'A',
+            Tile::Element(ElementTile::Earth) => 'E',
+            Tile::Binary(BinaryTile::Life) => 'L',
+            Tile::Binary(BinaryTile::Death) => 'D',
+            Tile::Theta => 'T',
+            Tile::Quicksilver => 'Q',
+            Tile::Metal(MetalTile::Lead) => '0',
+            Tile::Metal(MetalTile::Tin) => '1',
+            Tile::Metal(MetalTile::Iron) => '2',
+            Tile::Metal(MetalTile::Copper) => '3',
+            Tile::Metal(MetalTile::Silver) => '4',
+            Tile::Gold => '5',
+            Tile::Blocked => 'B',
+            #[cfg(feature = "quintessence")]
+            Tile::Quintessence => 'X',
+        }
+    }
+}
+
+impl Tile {
+    pub fn is_element(self) -> bool {
+        matches!(self, Tile::Element(_))
+    }
+    pub fn is_metal(self) -> bool {
+        matches!(self, Tile::Metal(_))
+    }
+    pub fn is_binary(self) -> bool {
+        matches!(self, Tile::Binary(_))
+    }
+    /// Whether this tile is cleared on its own rather than in a match, which is only gold.
+    pub fn is_clearable_alone(self) -> bool {
+        self == Tile::Gold
+    }
+    /// Whether this tile has to be cleared to win, i.e. it is neither empty nor blocked.
+    pub fn needs_clearing(self) -> bool {
+        !matches!(self, Tile::Empty | Tile::Blocked)
+    }
+    /// Whether this tile and `other` can be cleared as a pair under [`ClassicRules`], on a board
+    /// with `census`. The board decides which metal quicksilver can take.
+    pub fn can_match_with(self, other: Tile, census: &TileCensus) -> bool {
+        ClassicRules::default().pairs_with(self, other, census)
+    }
+    /// Whether this tile can be cleared on its own under [`ClassicRules`], on a board with
+    /// `census`. Only gold can.
+    pub fn can_clear_alone(self, census: &TileCensus) -> bool {
+        ClassicRules::default().clears_alone(self, census)
+    }
+    pub fn element(self) -> Option<ElementTile> {
+        match self {
+            Tile::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+    pub fn metal(self) -> Option<MetalTile> {
+        match self {
+            Tile::Metal(metal) => Some(metal),
+            _ => None,
+        }
+    }
+
+    /// The name of this tile in the game, e.g. `Vitae` or `Salt`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Tile::Empty => "Empty",
+            Tile::Theta => "Salt",
+            Tile::Element(ElementTile::Air) => "Air",
+            Tile::Element(ElementTile::Fire) => "Fire",
+            Tile::Element(ElementTile::Water) => "Water",
+            Tile::Element(ElementTile::Earth) => "Earth",
+            Tile::Binary(BinaryTile::Life) => "Vitae",
+            Tile::Binary(BinaryTile::Death) => "Mors",
+            Tile::Quicksilver => "Quicksilver",
+            Tile::Metal(MetalTile::Lead) => "Lead",
+            Tile::Metal(MetalTile::Tin) => "Tin",
+            Tile::Metal(MetalTile::Iron) => "Iron",
+            Tile::Metal(MetalTile::Copper) => "Copper",
+            Tile::Metal(MetalTile::Silver) => "Silver",
+            Tile::Gold => "Gold",
+            Tile::Blocked => "Blocked",
+            #[cfg(feature = "quintessence")]
+            Tile::Quintessence => "Quintessence",
+        }
+    }
+
+    /// How eager the solver is to clear this tile by default, with lower taken first and 50 as
+    /// neutral. `None` for cells that are never cleared.
+    ///
+    /// These set the [`PriorityWeights`](crate::PriorityWeights) of moves clearing one kind of
+    /// tile, and can be changed with [`Ruleset::base_priority`](crate::Ruleset::base_priority).
+    pub fn base_priority(self) -> Option<u32> {
+        match self {
+            Tile::Empty | Tile::Blocked => None,
+            // Always go for gold.
+            Tile::Gold => Some(0),
+            Tile::Metal(_) | Tile::Quicksilver => Some(50),
+            Tile::Element(_) | Tile::Binary(_) => Some(50),
+            // Salt can take any element, so it is worth keeping around.
+            Tile::Theta => Some(75),
+            #[cfg(feature = "quintessence")]
+            Tile::Quintessence => Some(50),
+        }
+    }
+
+    /// A tile chosen at random with the odds of the real game's distribution, so salt is half as
+    /// likely as any one element. This draws with replacement; use a
+    /// [`TileBag`](crate::TileBag) to deal a whole board.
+    pub fn sample_standard(rng: &mut impl Rng) -> Tile {
+        TileMultiset::standard()
+            .pick(rng)
+            .expect("the standard distribution has tiles")
+    }
+
+    /// A Unicode symbol for this tile, mostly the alchemical symbol used in the game.
+    pub fn glyph(self) -> char {
+        match self {
+            Tile::Empty => '·',
+            Tile::Element(ElementTile::Fire) => '🜂',
+            Tile::Element(ElementTile::Water) => '🜄',
+            Tile::Element(ElementTile::Air) => '🜁',
+            Tile::Element(ElementTile::Earth) => '🜃',
+            Tile::Binary(BinaryTile::Life) => '☥',
+            Tile::Binary(BinaryTile::Death) => '☠',
+            Tile::Theta => '🜔',
+            Tile::Quicksilver => '☿',
+            Tile::Metal(MetalTile::Lead) => '♄',
+            Tile::Metal(MetalTile::Tin) => '♃',
+            Tile::Metal(MetalTile::Iron) => '♂',
+            Tile::Metal(MetalTile::Copper) => '♀',
+            Tile::Metal(MetalTile::Silver) => '☽',
+            Tile::Gold => '☉',
+            Tile::Blocked => '▩',
+            #[cfg(feature = "quintessence")]
+            Tile::Quintessence => '🜀',
+        }
+    }
+}
+
+impl Tile {
+    /// A stable lowercase name for this tile, using the game's names (`"salt"`, `"vitae"`,
+    /// `"mors"`, ...). This does not change when variants are renamed or reordered.
+    pub fn key(self) -> &'static str {
+        match self {
+            Tile::Empty => "empty",
+            Tile::Theta => "salt",
+            Tile::Element(ElementTile::Air) => "air",
+            Tile::Element(ElementTile::Fire) => "fire",
+            Tile::Element(ElementTile::Water) => "water",
+            Tile::Element(ElementTile::Earth) => "earth",
+            Tile::Binary(BinaryTile::Life) => "vitae",
+            Tile::Binary(BinaryTile::Death) => "mors",
+            Tile::Quicksilver => "quicksilver",
+            Tile::Metal(MetalTile::Lead) => "lead",
+            Tile::Metal(MetalTile::Tin) => "tin",
+            Tile::Metal(MetalTile::Iron) => "iron",
+            Tile::Metal(MetalTile::Copper) => "copper",
+            Tile::Metal(MetalTile::Silver) => "silver",
+            Tile::Gold => "gold",
+            Tile::Blocked => "blocked",
+            #[cfg(feature = "quintessence")]
+            Tile::Quintessence => "quintessence",
+        }
+    }
+    /// A stable name for this tile's image in renderers, e.g. `"tile_fire"`, built from its
+    /// [`Tile::key`].
+    pub fn asset_key(self) -> String {
+        format!("tile_{}", self.key())
+    }
+    /// The tile with the given [`Tile::key`].
+    pub fn from_key(key: &str) -> Option<Tile> {
+        Tile::ALL.iter().copied().find(|tile| tile.key() == key)
+    }
+}
+
+/// Parses a [`Tile::key`], the variant names `theta`, `life` and `death`, or a single character
+/// from the board text format. Names are case-insensitive.
+impl std::str::FromStr for Tile {
+    type Err = TileParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Tile::try_from(c).map_err(|_| TileParseError(s.to_string()));
+        }
+        let name = s.to_ascii_lowercase();
+        let tile = match name.as_str() {
+            "theta" => Some(Tile::Theta),
+            "life" => Some(Tile::Binary(BinaryTile::Life)),
+            "death" => Some(Tile::Binary(BinaryTile::Death)),
+            name => Tile::from_key(name),
+        };
+        tile.ok_or_else(|| TileParseError(s.to_string()))
+    }
+}
+
+/// Serialized as its [`Tile::key`], e.g. `"fire"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Tile {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        serializer.serialize_str(self.key())
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Tile {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        Tile::from_key(&key)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown tile {:?}", key)))
+    }
+}
+
+/// Written as [`Tile::to_char`].
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// Matches under [`ClassicRules::default`].
 impl Matchable for Tile {
     fn filter_matches<'a, const S: usize, I>(
         &self,
         coord: &BoardCoord,
         board: &'a Board<S>,
-        mut candidates: I,
+        candidates: I,
     ) -> MatchSets
     where
         I: Iterator<Item = (BoardCoord, &'a Tile)>,
         [(); board_area::<S>()]:,
     {
-        match self {
-            Tile::Empty => MatchSets::default(),
-
-            Tile::Element(element_tile) => element_tile.filter_matches(coord, board, candidates),
-            // Covers matches with any other ElementTile, and other Thetas.
-            Tile::Theta => candidates
-                .filter_map(|(c, t)| match t {
-                    Tile::Theta if &c != coord => Some(c),
-                    Tile::Element(_) => Some(c),
-                    _ => None,
-                })
-                .map(|c| MatchSet::from([*coord, c]))
-                .collect(),
-
-            Tile::Binary(binary_tile) => binary_tile.filter_matches(coord, board, candidates),
-
-            // Metal matches are covered by below Quicksilver.
-            Tile::Metal(_) => MatchSets::default(),
-            // Gold, if selectable, is always clearable.
-            Tile::Gold => MatchSets::from([MatchSet::from([*coord])]),
-            Tile::Quicksilver => {
-                let earliest_metal = board.tiles().fold(None, |acc, val| match (acc, val) {
-                    (None, Tile::Metal(m)) => Some(m),
-                    (Some(m0), Tile::Metal(m1)) => {
-                        if (*m1 as u8) < (*m0 as u8) {
-                            Some(m1)
-                        } else {
-                            acc
-                        }
-                    }
-                    _ => acc,
-                });
-
-                candidates
-                    .find_map(|(c, t)| match (t, earliest_metal) {
-                        (Tile::Metal(m), Some(em)) if m == em => Some(c),
-                        _ => None,
-                    })
-                    .map(|c| MatchSets::from([MatchSet::from([*coord, c])]))
-                    .unwrap_or_default()
-            }
-        }
+        let candidates: Vec<_> = candidates.collect();
+        matches_for(
+            &ClassicRules::default(),
+            *coord,
+            *self,
+            &board.census(),
+            &candidates,
+        )
     }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::board::Board;
+    use crate::coord::MatchSet;
 
     #[test]
     fn test_element_tile_matches_same_type() {
@@ -240,4 +508,147 @@ mod tests {
             BoardCoord::new(2, 0)
         ])));
     }
+
+    #[test]
+    fn test_char_round_trip() {
+        for c in "_FWAELDTQ012345B".chars() {
+            let tile = Tile::try_from(c).unwrap();
+            assert_eq!(tile.to_char(), c);
+            assert_eq!(tile.to_string(), c.to_string());
+        }
+    }
+
+    #[test]
+    fn test_all_tiles() {
+        assert!(Tile::ALL.is_sorted_by(|a, b| a < b));
+        for tile in Tile::ALL {
+            assert_eq!(Tile::try_from(tile.to_char()), Ok(*tile));
+            assert_eq!(Tile::from_key(tile.key()), Some(*tile));
+            assert_eq!(Tile::from_u8(tile.to_u8()), Some(*tile));
+            assert_eq!(tile.asset_key(), format!("tile_{}", tile.key()));
+        }
+        let glyphs: std::collections::HashSet<char> = Tile::ALL.iter().map(|t| t.glyph()).collect();
+        assert_eq!(glyphs.len(), Tile::ALL.len());
+    }
+
+    #[test]
+    fn test_classification() {
+        let fire = Tile::Element(ElementTile::Fire);
+        assert!(fire.is_element() && !fire.is_metal() && !fire.is_binary());
+        assert_eq!(fire.element(), Some(ElementTile::Fire));
+        assert_eq!(fire.metal(), None);
+
+        let tin = Tile::Metal(MetalTile::Tin);
+        assert!(tin.is_metal() && !tin.is_element());
+        assert_eq!(tin.metal(), Some(MetalTile::Tin));
+
+        assert!(Tile::Binary(BinaryTile::Death).is_binary());
+        assert!(Tile::Gold.is_clearable_alone());
+        assert!(!Tile::Gold.is_metal());
+        assert!(!tin.is_clearable_alone());
+    }
+
+    #[test]
+    fn test_u8_codes_are_stable() {
+        assert_eq!(Tile::Empty.to_u8(), 0);
+        assert_eq!(Tile::Binary(BinaryTile::Death).to_u8(), 7);
+        assert_eq!(Tile::Gold.to_u8(), 14);
+        assert_eq!(Tile::Blocked.to_u8(), 16);
+        assert_eq!(Tile::from_u8(17), None);
+    }
+
+    #[test]
+    fn test_asset_keys() {
+        assert_eq!(Tile::Element(ElementTile::Fire).asset_key(), "tile_fire");
+        assert_eq!(Tile::Metal(MetalTile::Silver).asset_key(), "tile_silver");
+        assert_eq!(Tile::Theta.asset_key(), "tile_salt");
+    }
+
+    #[test]
+    fn test_can_match_with() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(0, 0), Tile::Metal(MetalTile::Tin)),
+            (BoardCoord::new(0, 2), Tile::Metal(MetalTile::Iron)),
+            (BoardCoord::new(2, 2), Tile::Gold),
+        ]);
+        let census = board.census();
+        let fire = Tile::Element(ElementTile::Fire);
+        assert!(fire.can_match_with(fire, &census));
+        assert!(fire.can_match_with(Tile::Theta, &census));
+        assert!(!fire.can_match_with(Tile::Element(ElementTile::Water), &census));
+        assert!(
+            Tile::Binary(BinaryTile::Life).can_match_with(Tile::Binary(BinaryTile::Death), &census)
+        );
+        assert!(Tile::Quicksilver.can_match_with(Tile::Metal(MetalTile::Tin), &census));
+        assert!(!Tile::Quicksilver.can_match_with(Tile::Metal(MetalTile::Iron), &census));
+        assert!(!Tile::Gold.can_match_with(Tile::Gold, &census));
+        assert!(Tile::Gold.can_clear_alone(&census));
+        assert!(!fire.can_clear_alone(&census));
+    }
+
+    #[test]
+    fn test_parse_names() {
+        assert_eq!("fire".parse(), Ok(Tile::Element(ElementTile::Fire)));
+        assert_eq!("Quicksilver".parse(), Ok(Tile::Quicksilver));
+        assert_eq!("salt".parse(), Ok(Tile::Theta));
+        assert_eq!("theta".parse(), Ok(Tile::Theta));
+        assert_eq!("vitae".parse(), Ok(Tile::Binary(BinaryTile::Life)));
+        assert_eq!("death".parse(), Ok(Tile::Binary(BinaryTile::Death)));
+        assert_eq!("copper".parse(), Ok(Tile::Metal(MetalTile::Copper)));
+        assert_eq!("3".parse(), Ok(Tile::Metal(MetalTile::Copper)));
+        assert_eq!("L".parse(), Ok(Tile::Binary(BinaryTile::Life)));
+        assert_eq!(
+            "brass".parse::<Tile>(),
+            Err(TileParseError("brass".to_string()))
+        );
+        assert!("".parse::<Tile>().is_err());
+        assert!("x".parse::<Tile>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_names() {
+        let tiles = [
+            Tile::Theta,
+            Tile::Binary(BinaryTile::Life),
+            Tile::Metal(MetalTile::Lead),
+        ];
+        let json = serde_json::to_string(&tiles).unwrap();
+        assert_eq!(json, r#"["salt","vitae","lead"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Tile>>(&json).unwrap(), tiles);
+        assert!(serde_json::from_str::<Tile>(r#""Theta""#).is_err());
+    }
+
+    #[cfg(feature = "quintessence")]
+    #[test]
+    fn test_quintessence_matches_one_of_each_element() {
+        let board = Board::<3>::from_iter([
+            (BoardCoord::new(2, 2), Tile::Quintessence),
+            (BoardCoord::new(0, 0), Tile::Element(ElementTile::Air)),
+            (BoardCoord::new(0, 2), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 0), Tile::Element(ElementTile::Fire)),
+            (BoardCoord::new(2, 4), Tile::Element(ElementTile::Water)),
+            (BoardCoord::new(4, 0), Tile::Element(ElementTile::Earth)),
+        ]);
+        let rules = ClassicRules {
+            quintessence: true,
+            ..ClassicRules::default()
+        };
+        assert!(
+            board
+                .find_match_sets()
+                .iter()
+                .all(|set| !set.contains(&BoardCoord::new(2, 2)))
+        );
+        let quintessence_sets: Vec<MatchSet> = board
+            .find_match_sets_with(&rules)
+            .into_iter()
+            .filter(|set| set.contains(&BoardCoord::new(2, 2)))
+            .collect();
+        assert_eq!(quintessence_sets.len(), 2);
+        assert!(quintessence_sets.iter().all(|set| set.len() == 5));
+        // The quintessence leaves one fire behind with no salt to pair it.
+        assert!(board.is_provably_unsolvable());
+        assert_eq!(Tile::try_from('X').unwrap(), Tile::Quintessence);
+    }
 }
@@ -1,4 +1,4 @@
-use sigmars_lib::{BinaryTile, Board, BoardCoord, ElementTile, Tile};
+use sigmars_lib::{BinaryTile, Board, BoardCoord, BoardDecodeError, ElementTile, Tile};
 use std::fs;
 use std::path::Path;
 use std::str::FromStr;
@@ -22,7 +22,13 @@ fn test_parse_good_boards() {
             continue;
         }
 
-        read_board_file(&path);
+        let board = read_board_file(&path);
+        assert_eq!(
+            board.validate_standard(),
+            Ok(()),
+            "Board file {:?} is not a standard board",
+            path
+        );
     }
 }
 
@@ -37,7 +43,7 @@ fn test_solve_good_boards() {
         }
 
         let mut board = read_board_file(&path);
-        let solution = sigmars_lib::solve_dfs(&board);
+        let solution = sigmars_lib::solve_dfs(&board).into_solution();
         assert!(
             solution.is_some(),
             "No solution found for board file {:?}",
@@ -88,3 +94,122 @@ fn test_parse_board1() {
         selectable_tiles.contains(&(BoardCoord::new(10, 3), &Tile::Element(ElementTile::Water)))
     );
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip() {
+    use sigmars_lib::MatchSet;
+
+    let board = read_board_file(&Path::new(GOOD_BOARD_DIR).join("board1.txt"));
+    let json = serde_json::to_string(&board).unwrap();
+    assert!(json.starts_with(r#"{"size":6,"tiles":[{"row":0,"col":2,"tile":"vitae"}"#));
+    assert_eq!(serde_json::from_str::<Board<6>>(&json).unwrap(), board);
+    assert!(serde_json::from_str::<Board<3>>(&json).is_err());
+    let twice =
+        r#"{"size":3,"tiles":[{"row":0,"col":0,"tile":"gold"},{"row":0,"col":0,"tile":"gold"}]}"#;
+    let err = serde_json::from_str::<Board<3>>(twice).unwrap_err();
+    assert!(err.to_string().contains("is given twice"), "{}", err);
+
+    let match_set = MatchSet::from([BoardCoord::new(3, 0), BoardCoord::new(0, 2)]);
+    let json = serde_json::to_string(&match_set).unwrap();
+    assert_eq!(json, r#"[{"row":0,"col":2},{"row":3,"col":0}]"#);
+    assert_eq!(serde_json::from_str::<MatchSet>(&json).unwrap(), match_set);
+    assert!(serde_json::from_str::<MatchSet>("[]").is_err());
+}
+
+#[test]
+fn test_parse_lenient() {
+    let board = read_board_file(&Path::new(GOOD_BOARD_DIR).join("board1.txt"));
+    let filedata = fs::read_to_string(Path::new(GOOD_BOARD_DIR).join("board1.txt")).unwrap();
+
+    // Indent the rows into a hexagon, with trailing spaces and comments around them.
+    let mut text = String::from("# board 1\n\n");
+    for (row, line) in filedata.lines().enumerate() {
+        text += &format!("{}{}  \n", " ".repeat(row.abs_diff(5)), line);
+    }
+    text += "# end\n";
+
+    assert_eq!(Board::<6>::from_str_lenient(&text).unwrap(), board);
+    assert!(Board::<6>::from_str(&text).is_err());
+
+    // A space is still an empty tile, even at the edge of an indented row.
+    let strict = "F F\n  W \n_____\n____\n___";
+    let expected = Board::<3>::from_str(strict).unwrap();
+    assert_eq!(Board::<3>::from_str_lenient(strict), Ok(expected.clone()));
+    let indented = "# spaces\n  F F\n   W \n_____\n ____  \n  ___\n";
+    assert_eq!(Board::<3>::from_str_lenient(indented), Ok(expected));
+}
+
+#[test]
+fn test_parse_visual() {
+    let board = read_board_file(&Path::new(GOOD_BOARD_DIR).join("board1.txt"));
+    let filedata = fs::read_to_string(Path::new(GOOD_BOARD_DIR).join("board1.txt")).unwrap();
+
+    // Lay the rows out as a hexagon with a margin, writing empty tiles as spaces.
+    let mut text = String::new();
+    for (row, line) in filedata.lines().enumerate() {
+        let indent = 4 + row.abs_diff(5);
+        let spaced: Vec<String> = line.replace('_', " ").chars().map(String::from).collect();
+        text += &format!("{}{}\n", " ".repeat(indent), spaced.join(" ").trim_end());
+    }
+
+    assert_eq!(Board::<6>::from_str_visual(&text).unwrap(), board);
+}
+
+#[test]
+fn test_compact_round_trip() {
+    let board = read_board_file(&Path::new(GOOD_BOARD_DIR).join("board1.txt"));
+    let compact = board.to_compact();
+    assert!(compact.starts_with("bLc/bAWc/"));
+    assert_eq!(Board::<6>::from_compact(&compact).unwrap(), board);
+
+    assert_eq!(Board::<3>::empty().to_compact(), "c/d/e/d/c");
+    assert!(Board::<3>::from_compact("c/d/e/d").is_err());
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let board = read_board_file(&Path::new(GOOD_BOARD_DIR).join("board1.txt"));
+    let bytes = board.to_bytes();
+    assert_eq!(bytes.len(), 2 + 46);
+    assert_eq!(Board::<6>::from_bytes(&bytes).unwrap(), board);
+
+    assert_eq!(
+        Board::<3>::from_bytes(&Board::<3>::empty().to_bytes()).unwrap(),
+        Board::<3>::empty()
+    );
+    assert_eq!(
+        Board::<5>::from_bytes(&bytes),
+        Err(BoardDecodeError::WrongSize {
+            expected: 5,
+            found: 6
+        })
+    );
+    assert_eq!(
+        Board::<6>::from_bytes(&bytes[..10]),
+        Err(BoardDecodeError::WrongLength {
+            expected: 48,
+            found: 10
+        })
+    );
+    let mut unknown = bytes.clone();
+    unknown[0] = 9;
+    assert_eq!(
+        Board::<6>::from_bytes(&unknown),
+        Err(BoardDecodeError::UnsupportedVersion(9))
+    );
+
+    let mut blocked = board.clone();
+    blocked.set_tile(BoardCoord::new(5, 5), Tile::Blocked);
+    let bytes = blocked.to_bytes();
+    assert_eq!(bytes[0], 2);
+    assert_eq!(bytes.len(), 2 + 91);
+    assert_eq!(Board::<6>::from_bytes(&bytes).unwrap(), blocked);
+
+    let mut bad_code = bytes.clone();
+    bad_code[2] = 200;
+    assert_eq!(
+        Board::<6>::from_bytes(&bad_code),
+        Err(BoardDecodeError::InvalidTileCode(200))
+    );
+}
@@ -0,0 +1,682 @@
+use std::fs;
+use std::str::FromStr;
+use std::time::Duration;
+
+use sigmars_lib::{
+    AnnotatedMatchSet, Board, BoardCoord, BoardParseError, CancelToken, ClassicRules, ElementTile,
+    EvictionPolicy, FewestElementThetas, FewestMoves, GreedyOutcome, Heuristics, MatchSet,
+    MoveOrdering, MoveType, PriorityWeights, Rejection, Ruleset, SearchProgress, SearchState,
+    Solution, SolutionError, SolutionParseError, Solvable, SolveOutcome, Solver, SolverOptions,
+    Strategy, Tile, TileCensus, TraceEvent, solve_dfs, solve_greedy, solve_many, solve_min_cost,
+    solve_portfolio,
+};
+
+#[test]
+fn test_greedy_solves_simple_board() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+
+    match solve_greedy(&board) {
+        GreedyOutcome::Solved(steps) => {
+            // Gold is always taken first.
+            assert_eq!(steps.len(), 2);
+            assert!(steps[0].contains(&BoardCoord::new(2, 2)));
+        }
+        GreedyOutcome::Stuck { .. } => panic!("Greedy solve got stuck on a trivial board"),
+    }
+}
+
+#[test]
+fn test_greedy_reports_stuck_board() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Water)),
+    ]);
+
+    match solve_greedy(&board) {
+        GreedyOutcome::Solved(_) => panic!("Mismatched elements cannot be solved"),
+        GreedyOutcome::Stuck { steps, state } => {
+            assert!(steps.is_empty());
+            assert_eq!(state, board);
+        }
+    }
+}
+
+#[test]
+fn test_bounded_dfs_respects_max_moves() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+
+    let bounded = |max_moves| Solver::new(SolverOptions::new().with_max_moves(max_moves));
+    assert!(matches!(
+        bounded(1).solve(&board),
+        SolveOutcome::Unsolvable { .. }
+    ));
+    assert_eq!(
+        bounded(2).solve(&board).into_solution().map(|s| s.len()),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_search_checkpoint_round_trip() {
+    let board = Board::<6>::from_str(
+        &fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/board1.txt"
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+
+    let mut search = SearchState::new(&board, &SolverOptions::default());
+    assert_eq!(search.advance(5), SearchProgress::Paused);
+
+    let mut checkpoint = Vec::new();
+    search.save(&mut checkpoint).unwrap();
+    let mut resumed = SearchState::<Board<6>>::resume(checkpoint.as_slice()).unwrap();
+    assert_eq!(resumed.states_explored(), search.states_explored());
+
+    let solution = resumed.advance(usize::MAX).into_solution().unwrap();
+    let mut board = board;
+    for step in &solution {
+        board.remove_match_set(step);
+    }
+    assert!(board.is_empty());
+}
+
+#[test]
+fn test_solve_many_preserves_order() {
+    let solvable = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+    ]);
+    let unsolvable = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Water)),
+    ]);
+
+    let outcomes = solve_many(
+        &[solvable.clone(), unsolvable, solvable],
+        &SolverOptions::new()
+            .with_threads(2)
+            .with_timeout(Duration::from_secs(10)),
+    );
+    assert!(matches!(outcomes[0], SolveOutcome::Solved(_)));
+    // Fire and water can never be paired, so the root is pruned as a dead end.
+    assert_eq!(
+        outcomes[1],
+        SolveOutcome::Unsolvable {
+            states_explored: 1,
+            dead_ends_pruned: 1,
+        }
+    );
+    assert!(matches!(outcomes[2], SolveOutcome::Solved(_)));
+}
+
+#[test]
+fn test_solution_verifies_and_replays() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+
+    let solution = board.solve().expect("Board should be solvable");
+    assert_eq!(solution.len(), 2);
+    assert_eq!(solution.moves()[0].move_type, MoveType::Gold);
+    assert_eq!(
+        solution.moves()[0].match_set.iter().collect::<Vec<_>>(),
+        vec![(BoardCoord::new(2, 2), Tile::Gold)]
+    );
+    assert_eq!(solution.verify(), Ok(()));
+    assert!(solution.iter_boards().last().unwrap().is_empty());
+    assert_eq!(solution.to_clock_notation(), "1. 5@0:0\n2. F@2:11, F@2:5\n");
+
+    let other = Board::<3>::from_iter([(BoardCoord::new(2, 2), Tile::Gold)]);
+    let replayed = Solution::from_steps(
+        &other,
+        solution
+            .iter()
+            .map(|step| step.match_set.match_set().clone()),
+    );
+    assert_eq!(replayed.verify(), Err(SolutionError::IllegalMove(1)));
+}
+
+#[test]
+fn test_cancelled_search() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(1, 1), Tile::Element(ElementTile::Water)),
+        (BoardCoord::new(3, 1), Tile::Element(ElementTile::Water)),
+    ]);
+    let cancel = CancelToken::new();
+    cancel.cancel();
+
+    let solver = Solver::new(SolverOptions::new().with_cancel_token(cancel));
+    assert_eq!(solver.solve(&board), SolveOutcome::Cancelled);
+}
+
+/// A root with many children, every one of which is a dead end.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DeadFan {
+    child: Option<u32>,
+}
+impl Solvable for DeadFan {
+    type Step = u32;
+
+    fn is_goal(&self) -> bool {
+        false
+    }
+    fn next_steps(&self) -> Vec<u32> {
+        match self.child {
+            None => (0..10_000).collect(),
+            Some(_) => Vec::new(),
+        }
+    }
+    fn apply_step(&self, step: &u32) -> Self {
+        DeadFan { child: Some(*step) }
+    }
+    fn is_dead_end(&self) -> bool {
+        self.child.is_some()
+    }
+}
+
+#[test]
+fn test_cancel_stops_a_run_of_rejected_states() {
+    let cancel = CancelToken::new();
+    cancel.cancel();
+    let solver = Solver::new(SolverOptions::new().with_cancel_token(cancel));
+    assert_eq!(
+        solver.solve(&DeadFan { child: None }),
+        SolveOutcome::Cancelled
+    );
+}
+
+#[test]
+fn test_priority_weights_change_move_order() {
+    // No move here is forced, so the weights decide what is tried first.
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 0), Tile::Theta),
+        (BoardCoord::new(2, 4), Tile::Theta),
+    ]);
+    let weights = PriorityWeights {
+        theta_theta: 0,
+        ..Default::default()
+    };
+
+    let steps = board.prioritized_steps(&weights);
+    assert_eq!(MoveType::identify(&board, &steps[0]), MoveType::ThetaTheta);
+
+    let solution = Solver::default()
+        .solve_weighted(&board, &weights)
+        .into_solution()
+        .expect("Board should be solvable");
+    assert_eq!(
+        MoveType::identify(&board, &solution[0]),
+        MoveType::ThetaTheta
+    );
+}
+
+#[test]
+fn test_ruleset_base_priority_sets_weights() {
+    struct SaltFirst;
+    impl Ruleset for SaltFirst {
+        fn pairs_with(&self, a: Tile, b: Tile, census: &TileCensus) -> bool {
+            ClassicRules::default().pairs_with(a, b, census)
+        }
+        fn clears_alone(&self, tile: Tile, census: &TileCensus) -> bool {
+            ClassicRules::default().clears_alone(tile, census)
+        }
+        fn base_priority(&self, tile: Tile) -> Option<u32> {
+            match tile {
+                Tile::Theta => Some(0),
+                tile => tile.base_priority(),
+            }
+        }
+    }
+
+    assert_eq!(PriorityWeights::for_rules(&SaltFirst).theta_theta, 0);
+    assert_eq!(
+        PriorityWeights::for_rules(&ClassicRules::default()),
+        PriorityWeights::default()
+    );
+    assert_eq!(PriorityWeights::default().gold, 0);
+    assert_eq!(PriorityWeights::default().theta_theta, 75);
+}
+
+#[test]
+fn test_solve_min_cost_avoids_element_thetas() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 0), Tile::Theta),
+        (BoardCoord::new(2, 4), Tile::Theta),
+    ]);
+
+    let solution = solve_min_cost(&board, &FewestElementThetas).unwrap();
+    assert_eq!(solution.cost, 0);
+    assert_eq!(
+        Solution::from_steps(&board, solution.steps).verify(),
+        Ok(())
+    );
+    // Every solution here takes two moves.
+    assert_eq!(solve_min_cost(&board, &FewestMoves).unwrap().cost, 2);
+}
+
+#[test]
+fn test_optimize_repairs_element_thetas() {
+    let fire = [BoardCoord::new(0, 0), BoardCoord::new(4, 2)];
+    let theta = [BoardCoord::new(2, 0), BoardCoord::new(2, 4)];
+    let board = Board::<3>::from_iter([
+        (fire[0], Tile::Element(ElementTile::Fire)),
+        (fire[1], Tile::Element(ElementTile::Fire)),
+        (theta[0], Tile::Theta),
+        (theta[1], Tile::Theta),
+    ]);
+    let solution = Solution::from_steps(
+        &board,
+        [
+            MatchSet::from([fire[0], theta[0]]),
+            MatchSet::from([fire[1], theta[1]]),
+        ],
+    );
+
+    let optimized = solution.optimize();
+    assert_eq!(optimized.verify(), Ok(()));
+    assert_eq!(optimized.len(), solution.len());
+    assert!(
+        optimized
+            .iter()
+            .all(|step| step.move_type != MoveType::ElementTheta)
+    );
+}
+
+#[test]
+fn test_annotated_match_set_keeps_tiles() {
+    let fire = Tile::Element(ElementTile::Fire);
+    let mut board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), fire),
+        (BoardCoord::new(4, 2), fire),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+    let match_set = MatchSet::from([BoardCoord::new(4, 2), BoardCoord::new(0, 0)]);
+    let annotated = AnnotatedMatchSet::new(&board, match_set.clone());
+    board.remove_match_set(&match_set);
+
+    assert_eq!(annotated.tile_at(&BoardCoord::new(4, 2)), Some(fire));
+    assert_eq!(annotated.tile_at(&BoardCoord::new(2, 2)), None);
+    assert_eq!(annotated.to_string(), "{F(0,0)+F(4,2)}");
+    assert_eq!(annotated.into_match_set(), match_set);
+
+    let solution = Solution::from_steps(&board, [MatchSet::single(BoardCoord::new(2, 2))]);
+    assert_eq!(solution.moves()[0].match_set.to_string(), "{5(2,2)}");
+}
+
+#[test]
+fn test_solution_moves_carry_metadata() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Theta),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+    let solution = Solution::from_steps(
+        &board,
+        [
+            MatchSet::single(BoardCoord::new(2, 2)),
+            MatchSet::from([BoardCoord::new(0, 0), BoardCoord::new(4, 2)]),
+        ],
+    );
+
+    let moves = solution.moves();
+    assert_eq!(moves.len(), 2);
+    assert_eq!(moves[1].index, 1);
+    assert_eq!(moves[1].move_type, MoveType::ElementTheta);
+    assert_eq!(moves[1].cost, 1);
+
+    let costed = solution.moves_with_cost(&FewestElementThetas);
+    assert_eq!(
+        costed.iter().map(|m| m.cost).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    assert_eq!(costed[0].match_set, solution.moves()[0].match_set);
+}
+
+#[test]
+fn test_solution_text_round_trip() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+    let solution = board.solve().unwrap();
+    let text = solution.to_string();
+    assert_eq!(text, "F__\n____\n__5__\n____\n__F\n\n2,2:5\n0,0:F 4,2:F\n");
+    assert_eq!(text.parse::<Solution<3>>(), Ok(solution.clone()));
+
+    // Tile annotations are optional, and can be names.
+    let bare = "F__\n____\n__5__\n____\n__F\n\n2,2\n\n4,2:fire 0,0\n";
+    assert_eq!(bare.parse::<Solution<3>>(), Ok(solution));
+
+    let board_text = "F__\n____\n__5__\n____\n__F\n";
+    assert_eq!(
+        format!("{board_text}\n0,0:F 4,2:W\n").parse::<Solution<3>>(),
+        Err(SolutionParseError::TileMismatch {
+            line: 7,
+            coord: BoardCoord::new(4, 2),
+            expected: Tile::Element(ElementTile::Water),
+            found: Tile::Element(ElementTile::Fire),
+        })
+    );
+    assert_eq!(
+        format!("{board_text}\n2,2 5,0\n").parse::<Solution<3>>(),
+        Err(SolutionParseError::OffBoard {
+            line: 7,
+            coord: BoardCoord::new(5, 0),
+        })
+    );
+    assert_eq!(
+        format!("{board_text}2,2\n").parse::<Solution<3>>(),
+        Err(SolutionParseError::MissingMoves)
+    );
+    assert_eq!(
+        "F__\n____\n".parse::<Solution<3>>(),
+        Err(SolutionParseError::Board(BoardParseError::InvalidRowCount(
+            5, 2
+        )))
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_solution_json_round_trip() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+    let solution = board.solve().unwrap();
+
+    let json = serde_json::to_value(&solution).unwrap();
+    assert_eq!(json["moves"].as_array().unwrap().len(), 2);
+    assert!(json["moves"][0]["move_type"].is_string());
+    let parsed: Solution<3> = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, solution);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_solution_steps_json_round_trip() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+    let solution = board.solve().unwrap();
+
+    let json = serde_json::to_string(solution.moves()).unwrap();
+    let moves: Vec<sigmars_lib::Move> = serde_json::from_str(&json).unwrap();
+    assert_eq!(moves, solution.moves());
+    let mismatched = r#"{"index":0,"match_set":[{"row":2,"col":2}],
+        "tiles":[{"row":0,"col":0,"tile":"gold"}],"move_type":"Gold","cost":1}"#;
+    let err = serde_json::from_str::<sigmars_lib::Move>(mismatched).unwrap_err();
+    assert!(
+        err.to_string().contains("one tile for each coordinate"),
+        "{}",
+        err
+    );
+
+    let match_set = MatchSet::from([BoardCoord::new(4, 2), BoardCoord::new(0, 0)]);
+    assert_eq!(
+        serde_json::to_string(&match_set).unwrap(),
+        r#"[{"row":0,"col":0},{"row":4,"col":2}]"#
+    );
+    let parsed: Vec<MatchSet> =
+        serde_json::from_str(r#"[[{"row":4,"col":2},{"row":0,"col":0}]]"#).unwrap();
+    assert_eq!(parsed, [match_set]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_priority_weights_deserialize_with_defaults() {
+    let weights: PriorityWeights = serde_json::from_str(r#"{ "gold": 7 }"#).unwrap();
+    assert_eq!(
+        weights,
+        PriorityWeights {
+            gold: 7,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_heuristics_steer_search() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 0), Tile::Theta),
+        (BoardCoord::new(2, 4), Tile::Theta),
+    ]);
+
+    // Prefer states with the fewest salts left, which the default priorities would try last.
+    let heuristics = Heuristics::new()
+        .order_by(|b: &Board<3>| b.tiles().filter(|t| **t == Tile::Theta).count() as u32);
+    let solution = Solver::default()
+        .solve_with_heuristics(&board, &heuristics)
+        .into_solution()
+        .expect("Board should be solvable");
+    assert_eq!(
+        MoveType::identify(&board, &solution[0]),
+        MoveType::ThetaTheta
+    );
+
+    let heuristics =
+        Heuristics::new().prune_above(0, |b: &Board<3>| b.nonempty_tiles().count() as u32);
+    assert!(matches!(
+        Solver::default().solve_with_heuristics(&board, &heuristics),
+        SolveOutcome::Unsolvable { .. }
+    ));
+}
+
+#[test]
+fn test_portfolio_returns_conclusive_outcome() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 2), Tile::Gold),
+    ]);
+    let (_, outcome) = solve_portfolio(&board);
+    assert_eq!(outcome.into_solution().map(|s| s.len()), Some(2));
+
+    // Greedy gets stuck here, so only an exhausted search can answer.
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Water)),
+    ]);
+    let (strategy, outcome) = solve_portfolio(&board);
+    assert!(matches!(strategy, Strategy::Search(_)));
+    assert!(matches!(outcome, SolveOutcome::Unsolvable { .. }));
+}
+
+#[test]
+fn test_trace_records_rejections() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Water)),
+        (BoardCoord::new(2, 0), Tile::Theta),
+        (BoardCoord::new(2, 4), Tile::Theta),
+    ]);
+
+    let (outcome, trace) = Solver::default().solve_traced(&board);
+    assert!(outcome.is_solved());
+
+    let TraceEvent::Expanded { depth, candidates } = &trace[0] else {
+        panic!("The root should be expanded first");
+    };
+    assert_eq!(*depth, 0);
+    assert_eq!(candidates.len(), 5);
+    assert!(candidates.iter().all(|c| c.priority.is_some()));
+
+    // Pairing the salts strands both elements, and is tried before any element/salt pair.
+    assert!(matches!(
+        &trace[1],
+        TraceEvent::Rejected {
+            depth: 1,
+            reason: Rejection::DeadEnd,
+            ..
+        }
+    ));
+}
+
+/// A corridor which can only be walked one cell at a time, with a branch into a wall at every cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Corridor {
+    position: u32,
+    length: u32,
+    in_wall: bool,
+}
+impl Solvable for Corridor {
+    type Step = bool;
+
+    fn is_goal(&self) -> bool {
+        self.position == self.length
+    }
+    fn next_steps(&self) -> Vec<bool> {
+        if self.in_wall {
+            Vec::new()
+        } else {
+            // Try walking into the wall first, so every level has to backtrack once.
+            vec![true, false]
+        }
+    }
+    fn apply_step(&self, into_wall: &bool) -> Self {
+        Corridor {
+            position: self.position + 1,
+            in_wall: *into_wall,
+            ..*self
+        }
+    }
+}
+
+#[test]
+fn test_deep_search_does_not_overflow_stack() {
+    let corridor = Corridor {
+        position: 0,
+        length: 200_000,
+        in_wall: false,
+    };
+    let solution = solve_dfs(&corridor)
+        .into_solution()
+        .expect("The corridor has an end");
+    assert_eq!(solution.len(), 200_000);
+    assert!(solution[..199_999].iter().all(|into_wall| !into_wall));
+}
+
+#[test]
+fn test_seen_limit_still_solves() {
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(0, 2), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(2, 0), Tile::Element(ElementTile::Water)),
+        (BoardCoord::new(2, 4), Tile::Element(ElementTile::Water)),
+        (BoardCoord::new(4, 0), Tile::Theta),
+        (BoardCoord::new(4, 2), Tile::Theta),
+    ]);
+
+    for policy in [
+        EvictionPolicy::KeepShallowest,
+        EvictionPolicy::LeastRecentlyUsed,
+    ] {
+        let options = SolverOptions::new().with_seen_limit(2, policy);
+        let mut search = SearchState::new(&board, &options);
+        assert_eq!(search.advance(1), SearchProgress::Paused);
+
+        // The limit and policy survive a checkpoint.
+        let mut checkpoint = Vec::new();
+        search.save(&mut checkpoint).unwrap();
+        let mut resumed = SearchState::<Board<3>>::resume(checkpoint.as_slice()).unwrap();
+        let solution = resumed.advance(usize::MAX).into_solution().unwrap();
+        assert_eq!(Solution::from_steps(&board, solution).verify(), Ok(()));
+    }
+}
+
+#[test]
+fn test_restarts_solve_and_exhaust() {
+    let restarts = Solver::new(SolverOptions::new().with_ordering(MoveOrdering::Restarts {
+        seed: 7,
+        restart_after: 1,
+    }));
+
+    let board = Board::<3>::from_iter([
+        (BoardCoord::new(0, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(0, 2), Tile::Element(ElementTile::Water)),
+        (BoardCoord::new(2, 0), Tile::Theta),
+        (BoardCoord::new(2, 4), Tile::Theta),
+        (BoardCoord::new(4, 0), Tile::Element(ElementTile::Fire)),
+        (BoardCoord::new(4, 2), Tile::Element(ElementTile::Air)),
+    ]);
+    let solution = restarts.solve(&board).into_solution().unwrap();
+    assert_eq!(Solution::from_steps(&board, solution).verify(), Ok(()));
+
+    // Every line of play through these levels runs out of steps.
+    let outcome = restarts.solve(&Levels(0));
+    assert!(matches!(outcome, SolveOutcome::Unsolvable { .. }));
+}
+
+/// A few levels of branching with no goal at the bottom.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Levels(u8);
+impl Solvable for Levels {
+    type Step = u8;
+
+    fn is_goal(&self) -> bool {
+        false
+    }
+    fn next_steps(&self) -> Vec<u8> {
+        if self.0 < 4 { vec![1, 2] } else { Vec::new() }
+    }
+    fn apply_step(&self, step: &u8) -> Self {
+        Levels((self.0 + step).min(4))
+    }
+}
+
+#[test]
+fn test_phased_solve() {
+    let board = Board::<6>::from_str(
+        &fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/board1.txt"
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+
+    let solution = Solver::default()
+        .solve_phased(&board)
+        .into_solution()
+        .unwrap();
+    assert_eq!(Solution::from_steps(&board, solution).verify(), Ok(()));
+}
+
+#[test]
+fn test_solve_is_deterministic() {
+    let input = fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/board1.txt"
+    ))
+    .unwrap();
+    // Each parse gets fresh hash sets, with their own iteration order.
+    let first = solve_dfs(&Board::<6>::from_str(&input).unwrap()).into_solution();
+    let second = solve_dfs(&Board::<6>::from_str(&input).unwrap()).into_solution();
+    assert!(first.is_some());
+    assert_eq!(first, second);
+}